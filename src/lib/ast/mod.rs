@@ -1,11 +1,16 @@
+#[cfg(test)]
 mod mod_test;
 
+use std::rc::Rc;
+
 use crate::token;
-use crate::token::Token;
+use crate::token::{Span, Token};
 
 pub trait Node {
     fn token_literal(&self) -> String;
     fn to_string(&self) -> String;
+    /// Source range covered by the node, including all of its children.
+    fn span(&self) -> Span;
 }
 
 pub trait StatementNode: Node {
@@ -16,22 +21,33 @@ pub trait ExpressionNode: Node {
     fn expression_node(&self);
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Expression {
     Identifier(Identifier),
     IntegerLiteral(IntegerLiteral),
+    #[cfg(feature = "bigint")]
+    BigIntegerLiteral(BigIntegerLiteral),
+    FloatLiteral(FloatLiteral),
+    StringLiteral(StringLiteral),
     PrefixExpression(PrefixExpression),
     InfixExpression(InfixExpression),
     Boolean(Boolean),
+    IfExpression(IfExpression),
+    FunctionLiteral(FunctionLiteral),
+    CallExpression(CallExpression),
+    ArrayLiteral(ArrayLiteral),
+    IndexExpression(IndexExpression),
+    HashLiteral(HashLiteral),
     // Add other variants similarly
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct IntegerLiteral {
     pub token: Token,
     pub value: i64,
 }
 
+#[allow(clippy::needless_return)]
 impl Node for IntegerLiteral {
     fn token_literal(&self) -> String {
         return self.token.literal.clone();
@@ -39,28 +55,99 @@ impl Node for IntegerLiteral {
     fn to_string(&self) -> String {
         return self.value.to_string();
     }
+    fn span(&self) -> Span {
+        self.token.span
+    }
+}
+
+/// Integer literal too large for `i64`, only parsed with the `bigint`
+/// feature.
+#[cfg(feature = "bigint")]
+#[derive(Debug, Clone)]
+pub struct BigIntegerLiteral {
+    pub token: Token,
+    pub value: num_bigint::BigInt,
+}
+
+#[cfg(feature = "bigint")]
+impl Node for BigIntegerLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+    fn span(&self) -> Span {
+        self.token.span
+    }
+}
+
+/// Float literal; `to_string` keeps the source spelling (`.5`, `1e-9`)
+/// rather than re-printing the parsed value.
+#[derive(Debug, Clone)]
+pub struct FloatLiteral {
+    pub token: Token,
+    pub value: f64,
+}
+
+impl Node for FloatLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+    fn to_string(&self) -> String {
+        self.token.literal.clone()
+    }
+    fn span(&self) -> Span {
+        self.token.span
+    }
 }
 
-#[derive(Debug)]
+/// String literal; `value` holds the text with escape sequences decoded
+/// while the token literal keeps the quoted source spelling.
+#[derive(Debug, Clone)]
+pub struct StringLiteral {
+    pub token: Token,
+    pub value: String,
+}
+
+impl Node for StringLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+    fn to_string(&self) -> String {
+        self.token.literal.clone()
+    }
+    fn span(&self) -> Span {
+        self.token.span
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct PrefixExpression {
     pub token: Token,
     pub operator: String,
     pub right: Box<Expression>,
 }
 
+#[allow(clippy::needless_return)]
 impl Node for PrefixExpression {
     fn token_literal(&self) -> String {
         return self.token.literal.clone();
     }
     fn to_string(&self) -> String {
         let mut out = String::new();
+        out.push('(');
         out.push_str(&self.operator);
         out.push_str(&self.right.to_string());
+        out.push(')');
         out
     }
+    fn span(&self) -> Span {
+        self.token.span.to(self.right.span())
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct InfixExpression {
     pub token: Token,
     pub left: Box<Expression>,
@@ -68,25 +155,34 @@ pub struct InfixExpression {
     pub right: Box<Expression>,
 }
 
+#[allow(clippy::needless_return)]
 impl Node for InfixExpression {
     fn token_literal(&self) -> String {
         return self.token.literal.clone();
     }
     fn to_string(&self) -> String {
         let mut out = String::new();
+        out.push('(');
         out.push_str(&self.left.to_string());
+        out.push(' ');
         out.push_str(&self.operator);
+        out.push(' ');
         out.push_str(&self.right.to_string());
+        out.push(')');
         out
     }
+    fn span(&self) -> Span {
+        self.left.span().to(self.right.span())
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Boolean {
     pub token: Token,
     pub value: bool,
 }
 
+#[allow(clippy::needless_return)]
 impl Node for Boolean {
     fn token_literal(&self) -> String {
         return self.token.literal.clone();
@@ -94,18 +190,234 @@ impl Node for Boolean {
     fn to_string(&self) -> String {
         return self.value.to_string();
     }
+    fn span(&self) -> Span {
+        self.token.span
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IfExpression {
+    pub token: Token,
+    pub condition: Box<Expression>,
+    pub consequence: Box<BlockStatement>,
+    pub alternative: Option<Box<BlockStatement>>,
+}
+
+impl Node for IfExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+    fn to_string(&self) -> String {
+        let mut out = String::new();
+        out.push_str("if ");
+        out.push_str(&self.condition.to_string());
+        out.push(' ');
+        out.push_str(&self.consequence.to_string());
+        if let Some(alternative) = &self.alternative {
+            out.push_str(" else ");
+            out.push_str(&alternative.to_string());
+        }
+        out
+    }
+    fn span(&self) -> Span {
+        match &self.alternative {
+            Some(alternative) => self.token.span.to(alternative.span()),
+            None => self.token.span.to(self.consequence.span()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FunctionLiteral {
+    pub token: Token,
+    pub parameters: Vec<Identifier>,
+    pub body: Rc<BlockStatement>,
+}
+
+impl Node for FunctionLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+    fn to_string(&self) -> String {
+        let parameters: Vec<String> = self.parameters.iter().map(|parameter| parameter.to_string()).collect();
+
+        let mut out = String::new();
+        out.push_str(&self.token_literal());
+        out.push('(');
+        out.push_str(&parameters.join(", "));
+        out.push_str(") ");
+        out.push_str(&self.body.to_string());
+        out
+    }
+    fn span(&self) -> Span {
+        self.token.span.to(self.body.span())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CallExpression {
+    pub token: Token,
+    pub function: Box<Expression>,
+    pub arguments: Vec<Expression>,
+    pub closing: Span,
+}
+
+impl Node for CallExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+    fn to_string(&self) -> String {
+        let arguments: Vec<String> = self.arguments.iter().map(|argument| argument.to_string()).collect();
+
+        let mut out = String::new();
+        out.push_str(&self.function.to_string());
+        out.push('(');
+        out.push_str(&arguments.join(", "));
+        out.push(')');
+        out
+    }
+    fn span(&self) -> Span {
+        self.function.span().to(self.closing)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ArrayLiteral {
+    pub token: Token,
+    pub elements: Vec<Expression>,
+    pub closing: Span,
+}
+
+impl Node for ArrayLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+    fn to_string(&self) -> String {
+        let elements: Vec<String> = self.elements.iter().map(|element| element.to_string()).collect();
+
+        let mut out = String::new();
+        out.push('[');
+        out.push_str(&elements.join(", "));
+        out.push(']');
+        out
+    }
+    fn span(&self) -> Span {
+        self.token.span.to(self.closing)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IndexExpression {
+    pub token: Token,
+    pub left: Box<Expression>,
+    pub index: Box<Expression>,
+    pub closing: Span,
+}
+
+impl Node for IndexExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+    fn to_string(&self) -> String {
+        let mut out = String::new();
+        out.push('(');
+        out.push_str(&self.left.to_string());
+        out.push('[');
+        out.push_str(&self.index.to_string());
+        out.push_str("])");
+        out
+    }
+    fn span(&self) -> Span {
+        self.left.span().to(self.closing)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HashLiteral {
+    pub token: Token,
+    pub pairs: Vec<(Expression, Expression)>,
+    pub closing: Span,
+}
+
+impl Node for HashLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+    fn to_string(&self) -> String {
+        let pairs: Vec<String> = self.pairs.iter().map(|(key, value)| format!("{}: {}", key.to_string(), value.to_string())).collect();
+
+        let mut out = String::new();
+        out.push('{');
+        out.push_str(&pairs.join(", "));
+        out.push('}');
+        out
+    }
+    fn span(&self) -> Span {
+        self.token.span.to(self.closing)
+    }
 }
 
 impl Node for Expression {
     fn token_literal(&self) -> String {
-        return String::new();
+        match self {
+            Expression::Identifier(expression) => expression.token_literal(),
+            Expression::IntegerLiteral(expression) => expression.token_literal(),
+            #[cfg(feature = "bigint")]
+            Expression::BigIntegerLiteral(expression) => expression.token_literal(),
+            Expression::FloatLiteral(expression) => expression.token_literal(),
+            Expression::StringLiteral(expression) => expression.token_literal(),
+            Expression::PrefixExpression(expression) => expression.token_literal(),
+            Expression::InfixExpression(expression) => expression.token_literal(),
+            Expression::Boolean(expression) => expression.token_literal(),
+            Expression::IfExpression(expression) => expression.token_literal(),
+            Expression::FunctionLiteral(expression) => expression.token_literal(),
+            Expression::CallExpression(expression) => expression.token_literal(),
+            Expression::ArrayLiteral(expression) => expression.token_literal(),
+            Expression::IndexExpression(expression) => expression.token_literal(),
+            Expression::HashLiteral(expression) => expression.token_literal(),
+        }
     }
     fn to_string(&self) -> String {
-        return String::new();
+        match self {
+            Expression::Identifier(expression) => expression.to_string(),
+            Expression::IntegerLiteral(expression) => expression.to_string(),
+            #[cfg(feature = "bigint")]
+            Expression::BigIntegerLiteral(expression) => expression.to_string(),
+            Expression::FloatLiteral(expression) => expression.to_string(),
+            Expression::StringLiteral(expression) => expression.to_string(),
+            Expression::PrefixExpression(expression) => expression.to_string(),
+            Expression::InfixExpression(expression) => expression.to_string(),
+            Expression::Boolean(expression) => expression.to_string(),
+            Expression::IfExpression(expression) => expression.to_string(),
+            Expression::FunctionLiteral(expression) => expression.to_string(),
+            Expression::CallExpression(expression) => expression.to_string(),
+            Expression::ArrayLiteral(expression) => expression.to_string(),
+            Expression::IndexExpression(expression) => expression.to_string(),
+            Expression::HashLiteral(expression) => expression.to_string(),
+        }
+    }
+    fn span(&self) -> Span {
+        match self {
+            Expression::Identifier(expression) => expression.span(),
+            Expression::IntegerLiteral(expression) => expression.span(),
+            #[cfg(feature = "bigint")]
+            Expression::BigIntegerLiteral(expression) => expression.span(),
+            Expression::FloatLiteral(expression) => expression.span(),
+            Expression::StringLiteral(expression) => expression.span(),
+            Expression::PrefixExpression(expression) => expression.span(),
+            Expression::InfixExpression(expression) => expression.span(),
+            Expression::Boolean(expression) => expression.span(),
+            Expression::IfExpression(expression) => expression.span(),
+            Expression::FunctionLiteral(expression) => expression.span(),
+            Expression::CallExpression(expression) => expression.span(),
+            Expression::ArrayLiteral(expression) => expression.span(),
+            Expression::IndexExpression(expression) => expression.span(),
+            Expression::HashLiteral(expression) => expression.span(),
+        }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Statement {
     LetStatement(LetStatement),
     ReturnStatement(ReturnStatement),
@@ -130,6 +442,13 @@ impl Node for Statement {
             // Handle other variants similarly
         }
     }
+    fn span(&self) -> Span {
+        match self {
+            Statement::LetStatement(stmt) => stmt.span(),
+            Statement::ReturnStatement(stmt) => stmt.span(),
+            Statement::ExpressionStatement(stmt) => stmt.span(),
+        }
+    }
 }
 
 pub struct Program {
@@ -153,15 +472,47 @@ impl Node for Program {
         }
         out
     }
+    fn span(&self) -> Span {
+        match (self.statements.first(), self.statements.last()) {
+            (Some(first), Some(last)) => first.span().to(last.span()),
+            _ => Span::default(),
+        }
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+pub struct BlockStatement {
+    pub token: Token,
+    pub statements: Vec<Statement>,
+    pub closing: Span,
+}
+
+impl Node for BlockStatement {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+    fn to_string(&self) -> String {
+        let mut out = String::new();
+        out.push_str("{ ");
+        for stmt in &self.statements {
+            out.push_str(&stmt.to_string());
+        }
+        out.push_str(" }");
+        out
+    }
+    fn span(&self) -> Span {
+        self.token.span.to(self.closing)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct LetStatement {
     pub token: Token,
     pub name: Identifier,
     pub value: Option<Expression>,
 }
 
+#[allow(clippy::needless_return, clippy::single_char_add_str)]
 impl Node for LetStatement {
     fn token_literal(&self) -> String {
         return self.token.literal.clone();
@@ -183,14 +534,21 @@ impl Node for LetStatement {
         out.push_str(";");
         out
     }
+    fn span(&self) -> Span {
+        match &self.value {
+            Some(value) => self.token.span.to(value.span()),
+            None => self.token.span.to(self.name.span()),
+        }
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ReturnStatement {
     pub token: token::Token,
     pub return_value: Option<Expression>,
 }
 
+#[allow(clippy::needless_return, clippy::single_char_add_str)]
 impl Node for ReturnStatement {
     fn token_literal(&self) -> String {
         return self.token.literal.clone();
@@ -208,14 +566,21 @@ impl Node for ReturnStatement {
         out.push_str(";");
         out
     }
+    fn span(&self) -> Span {
+        match &self.return_value {
+            Some(return_value) => self.token.span.to(return_value.span()),
+            None => self.token.span,
+        }
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ExpressionStatement {
     pub token: Token,
     pub expression: Option<Expression>,
 }
 
+#[allow(clippy::needless_return)]
 impl Node for ExpressionStatement {
     fn token_literal(&self) -> String {
         return self.token.literal.clone();
@@ -226,9 +591,15 @@ impl Node for ExpressionStatement {
             None => String::from("expression not_found"),
         }
     }
+    fn span(&self) -> Span {
+        match &self.expression {
+            Some(expression) => self.token.span.to(expression.span()),
+            None => self.token.span,
+        }
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Identifier {
     pub token: Token,
     pub value: String,
@@ -241,4 +612,7 @@ impl Node for Identifier {
     fn to_string(&self) -> String {
         self.value.clone()
     }
+    fn span(&self) -> Span {
+        self.token.span
+    }
 }
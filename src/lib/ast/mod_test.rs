@@ -1,5 +1,8 @@
-use crate::ast::{Expression, Identifier, LetStatement, Node, Program, ReturnStatement, Statement};
-use crate::token::{Token, TokenType};
+use crate::ast::{
+    Expression, ExpressionStatement, Identifier, InfixExpression, LetStatement, Node, PrefixExpression, Program, ReturnStatement,
+    Statement,
+};
+use crate::token::{Span, Token, TokenType};
 
 
 #[test]
@@ -10,11 +13,13 @@ fn test_to_string() {
                 token: Token {
                     token_type: TokenType::LET,
                     literal: String::from("let"),
+                    span: Span::default(),
                 },
                 name: Identifier {
                     token: Token {
                         token_type: TokenType::IDENT,
                         literal: String::from("myVar"),
+                        span: Span::default(),
                     },
                     value: String::from("myVar"),
                 },
@@ -24,6 +29,7 @@ fn test_to_string() {
                 token: Token {
                     token_type: TokenType::RETURN,
                     literal: String::from("return"),
+                    span: Span::default(),
                 },
                 return_value: None,
             }),
@@ -32,4 +38,39 @@ fn test_to_string() {
     if program.to_string() != "let myVar = ;return ;" {
         panic!("program.to_string() wrong. got={}", program.to_string());
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_expression_to_string() {
+    let identifier = |name: &str| {
+        Expression::Identifier(Identifier {
+            token: Token { token_type: TokenType::IDENT, literal: String::from(name), span: Span::default() },
+            value: String::from(name),
+        })
+    };
+    let expression = Expression::InfixExpression(InfixExpression {
+        token: Token { token_type: TokenType::ASTERISK, literal: String::from("*"), span: Span::default() },
+        left: Box::new(Expression::PrefixExpression(PrefixExpression {
+            token: Token { token_type: TokenType::MINUS, literal: String::from("-"), span: Span::default() },
+            operator: String::from("-"),
+            right: Box::new(identifier("a")),
+        })),
+        operator: String::from("*"),
+        right: Box::new(identifier("b")),
+    });
+
+    if expression.to_string() != "((-a) * b)" {
+        panic!("expression.to_string() wrong. got={}", expression.to_string());
+    }
+    if expression.token_literal() != "*" {
+        panic!("expression.token_literal() wrong. got={}", expression.token_literal());
+    }
+
+    let statement = Statement::ExpressionStatement(ExpressionStatement {
+        token: Token { token_type: TokenType::MINUS, literal: String::from("-"), span: Span::default() },
+        expression: Some(expression),
+    });
+    if statement.to_string() != "((-a) * b)" {
+        panic!("statement.to_string() wrong. got={}", statement.to_string());
+    }
+}
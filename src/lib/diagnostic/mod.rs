@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod mod_test;
+
+use std::fmt;
+
+use crate::token::Span;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// Additional source location attached to a diagnostic, e.g. "opened here".
+#[derive(Debug, PartialEq, Clone)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: &'static str,
+    pub message: String,
+    pub span: Span,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn error(code: &'static str, message: String, span: Span) -> Diagnostic {
+        Diagnostic { severity: Severity::Error, code, message, span, labels: Vec::new(), notes: Vec::new() }
+    }
+
+    pub fn with_label(mut self, span: Span, message: &str) -> Diagnostic {
+        self.labels.push(Label { span, message: message.to_string() });
+        self
+    }
+
+    pub fn with_help(mut self, note: &str) -> Diagnostic {
+        self.notes.push(note.to_string());
+        self
+    }
+
+    /// Renders the diagnostic the way rustc does: a header, the location, and
+    /// every labelled source line with the offending range underlined.
+    pub fn render(&self, name: &str, source: &str) -> String {
+        let lines: Vec<&str> = source.lines().collect();
+        let last_line = self.labels.iter().map(|label| label.span.line).fold(self.span.line, usize::max);
+        let gutter = " ".repeat(last_line.to_string().len());
+
+        let mut out = String::new();
+        out.push_str(&format!("{}[{}]: {}\n", self.severity, self.code, self.message));
+        out.push_str(&format!("{}--> {}:{}\n", gutter, name, self.span));
+        out.push_str(&format!("{} |\n", gutter));
+
+        let mut annotations = vec![(self.span, '^', "")];
+        annotations.extend(self.labels.iter().map(|label| (label.span, '-', label.message.as_str())));
+        annotations.sort_by_key(|(span, _, _)| span.start);
+
+        let mut previous_line = 0;
+        for (span, marker, message) in annotations {
+            let text = lines.get(span.line.wrapping_sub(1)).copied().unwrap_or("");
+            let indent: String = text.chars().take(span.column.saturating_sub(1)).map(|c| if c == '\t' { '\t' } else { ' ' }).collect();
+            let available = text.chars().count().saturating_sub(span.column.saturating_sub(1));
+            let width = source.get(span.start..span.end).map_or(0, |s| s.chars().count()).min(available).max(1);
+
+            if span.line != previous_line {
+                out.push_str(&format!("{:>width$} | {}\n", span.line, text, width = gutter.len()));
+                previous_line = span.line;
+            }
+            out.push_str(&format!("{} | {}{}", gutter, indent, marker.to_string().repeat(width)));
+            if !message.is_empty() {
+                out.push(' ');
+                out.push_str(message);
+            }
+            out.push('\n');
+        }
+
+        for note in &self.notes {
+            out.push_str(&format!("{} = help: {}\n", gutter, note));
+        }
+        out
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.span, self.message)
+    }
+}
@@ -0,0 +1,66 @@
+use crate::diagnostic::Diagnostic;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::token::Span;
+
+#[test]
+fn test_render_primary_span() {
+    let source = "let x = 1;\nlet = 5;";
+    let diagnostic = Diagnostic::error("E0001", String::from("expected next token to be IDENT, got ASSIGN instead"), Span::new(15, 16, 2, 5));
+
+    let expected = "error[E0001]: expected next token to be IDENT, got ASSIGN instead
+ --> main.monkey:2:5
+  |
+2 | let = 5;
+  |     ^
+";
+    let rendered = diagnostic.render("main.monkey", source);
+    if rendered != expected {
+        panic!("rendered diagnostic wrong.\nexpected:\n{}\ngot:\n{}", expected, rendered);
+    }
+}
+
+#[test]
+fn test_render_labels_and_help() {
+    let source = "let total = 99999999999999999999;";
+    let diagnostic = Diagnostic::error("E0003", String::from("could not parse 99999999999999999999 as integer"), Span::new(12, 32, 1, 13))
+        .with_label(Span::new(4, 9, 1, 5), "while parsing the value of this binding")
+        .with_help("integers must fit in 64 bits");
+
+    let expected = "error[E0003]: could not parse 99999999999999999999 as integer
+ --> <repl>:1:13
+  |
+1 | let total = 99999999999999999999;
+  |     ----- while parsing the value of this binding
+  |             ^^^^^^^^^^^^^^^^^^^^
+  = help: integers must fit in 64 bits
+";
+    let rendered = diagnostic.render("<repl>", source);
+    if rendered != expected {
+        panic!("rendered diagnostic wrong.\nexpected:\n{}\ngot:\n{}", expected, rendered);
+    }
+}
+
+#[test]
+fn test_parser_diagnostics_render() {
+    let source = "let x = 1;\n  let 5 = 2;";
+    let mut parser = Parser::new(Lexer::new(source));
+    parser.parse_program();
+
+    let diagnostics = parser.diagnostics();
+    if diagnostics.is_empty() {
+        panic!("parser produced no diagnostics");
+    }
+
+    let expected = "error[E0001]: expected next token to be IDENT, got INT instead
+ --> <repl>:2:7
+  |
+2 |   let 5 = 2;
+  |   --- after this LET
+  |       ^
+";
+    let rendered = diagnostics[0].render("<repl>", source);
+    if rendered != expected {
+        panic!("rendered diagnostic wrong.\nexpected:\n{}\ngot:\n{}", expected, rendered);
+    }
+}
@@ -0,0 +1,101 @@
+use num_bigint::BigInt;
+use num_traits::{Pow, Signed, ToPrimitive, Zero};
+
+use crate::evaluator::object::Object;
+
+/// Largest result, in bits, that `*`, `**` and `<<` may produce (about
+/// 315,000 decimal digits). Bigger results would take so long or need so
+/// much memory that a single expression could hang or abort the interpreter.
+const MAX_BITS: u64 = 1 << 20;
+
+/// Wraps `value` as an object, narrowing it back to `Object::Integer` when
+/// it fits in 64 bits so both representations compare and hash alike.
+pub fn normalize(value: BigInt) -> Object {
+    match value.to_i64() {
+        Some(value) => Object::Integer(value),
+        None => Object::BigInt(value),
+    }
+}
+
+pub fn to_f64(value: &BigInt) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+pub fn eval_prefix_expression(operator: &str, value: BigInt) -> Object {
+    match operator {
+        "-" => normalize(-value),
+        "~" => normalize(!value),
+        _ => Object::Error(format!("unknown operator: {}INTEGER", operator)),
+    }
+}
+
+/// Evaluates an integer operator exactly. Division and remainder truncate
+/// like their 64-bit counterparts.
+pub fn eval_infix_expression(operator: &str, left: BigInt, right: BigInt) -> Object {
+    match operator {
+        "+" => normalize(left + right),
+        "-" => normalize(left - right),
+        "*" => {
+            if left.bits() + right.bits() > MAX_BITS + 1 {
+                return Object::Error(String::from("integer result too large"));
+            }
+            normalize(left * right)
+        }
+        "/" => {
+            if right.is_zero() {
+                return Object::Error(String::from("division by zero"));
+            }
+            normalize(left / right)
+        }
+        "%" => {
+            if right.is_zero() {
+                return Object::Error(String::from("modulo by zero"));
+            }
+            normalize(left % right)
+        }
+        "**" => {
+            if right.is_negative() {
+                return Object::Error(format!("negative exponent: {}", right));
+            }
+            // The result has at least `exponent * (bits - 1) + 1` bits, which
+            // also lets 0, 1 and -1 take any exponent.
+            let significant_bits = left.bits().saturating_sub(1);
+            match right.to_u64() {
+                Some(exponent) if exponent.saturating_mul(significant_bits) <= MAX_BITS => normalize(Pow::pow(left, exponent)),
+                _ => Object::Error(format!("exponent too large: {}", right)),
+            }
+        }
+        "<<" => {
+            if right.is_negative() {
+                return Object::Error(format!("negative shift amount: {}", right));
+            }
+            if left.is_zero() {
+                return Object::Integer(0);
+            }
+            match right.to_u64() {
+                Some(shift) if left.bits().saturating_add(shift) <= MAX_BITS => normalize(left << shift),
+                _ => Object::Error(format!("shift amount too large: {}", right)),
+            }
+        }
+        // Shifting right by at least the width leaves only the sign.
+        ">>" => {
+            if right.is_negative() {
+                return Object::Error(format!("negative shift amount: {}", right));
+            }
+            match right.to_u64() {
+                Some(shift) if shift < left.bits() => normalize(left >> shift),
+                _ => Object::Integer(if left.is_negative() { -1 } else { 0 }),
+            }
+        }
+        "&" => normalize(left & right),
+        "|" => normalize(left | right),
+        "^" => normalize(left ^ right),
+        "<" => Object::Boolean(left < right),
+        ">" => Object::Boolean(left > right),
+        "<=" => Object::Boolean(left <= right),
+        ">=" => Object::Boolean(left >= right),
+        "==" => Object::Boolean(left == right),
+        "!=" => Object::Boolean(left != right),
+        _ => Object::Error(format!("unknown operator: INTEGER {} INTEGER", operator)),
+    }
+}
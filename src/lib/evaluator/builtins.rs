@@ -0,0 +1,79 @@
+use crate::evaluator::object::{Builtin, Object};
+
+const BUILTINS: &[Builtin] = &[
+    Builtin { name: "len", func: len },
+    Builtin { name: "first", func: first },
+    Builtin { name: "last", func: last },
+    Builtin { name: "rest", func: rest },
+    Builtin { name: "push", func: push },
+];
+
+pub fn lookup(name: &str) -> Option<Object> {
+    BUILTINS.iter().find(|builtin| builtin.name == name).map(|builtin| Object::Builtin(*builtin))
+}
+
+fn wrong_number_of_arguments(got: usize, want: usize) -> Object {
+    Object::Error(format!("wrong number of arguments. got={}, want={}", got, want))
+}
+
+fn len(arguments: Vec<Object>) -> Object {
+    if arguments.len() != 1 {
+        return wrong_number_of_arguments(arguments.len(), 1);
+    }
+
+    match &arguments[0] {
+        Object::String(value) => Object::Integer(value.chars().count() as i64),
+        Object::Array(elements) => Object::Integer(elements.len() as i64),
+        other => Object::Error(format!("argument to `len` not supported, got {}", other.type_name())),
+    }
+}
+
+fn first(arguments: Vec<Object>) -> Object {
+    if arguments.len() != 1 {
+        return wrong_number_of_arguments(arguments.len(), 1);
+    }
+
+    match &arguments[0] {
+        Object::Array(elements) => elements.first().cloned().unwrap_or(Object::Null),
+        other => Object::Error(format!("argument to `first` must be ARRAY, got {}", other.type_name())),
+    }
+}
+
+fn last(arguments: Vec<Object>) -> Object {
+    if arguments.len() != 1 {
+        return wrong_number_of_arguments(arguments.len(), 1);
+    }
+
+    match &arguments[0] {
+        Object::Array(elements) => elements.last().cloned().unwrap_or(Object::Null),
+        other => Object::Error(format!("argument to `last` must be ARRAY, got {}", other.type_name())),
+    }
+}
+
+fn rest(arguments: Vec<Object>) -> Object {
+    if arguments.len() != 1 {
+        return wrong_number_of_arguments(arguments.len(), 1);
+    }
+
+    match &arguments[0] {
+        Object::Array(elements) if elements.is_empty() => Object::Null,
+        Object::Array(elements) => Object::Array(elements[1..].to_vec()),
+        other => Object::Error(format!("argument to `rest` must be ARRAY, got {}", other.type_name())),
+    }
+}
+
+fn push(arguments: Vec<Object>) -> Object {
+    if arguments.len() != 2 {
+        return wrong_number_of_arguments(arguments.len(), 2);
+    }
+
+    let mut arguments = arguments.into_iter();
+    match (arguments.next(), arguments.next()) {
+        (Some(Object::Array(mut elements)), Some(element)) => {
+            elements.push(element);
+            Object::Array(elements)
+        }
+        (Some(other), _) => Object::Error(format!("argument to `push` must be ARRAY, got {}", other.type_name())),
+        _ => unreachable!("argument count checked above"),
+    }
+}
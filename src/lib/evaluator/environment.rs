@@ -0,0 +1,103 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::evaluator::object::Object;
+
+/// What integer arithmetic does when a result does not fit in 64 bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    /// Wrap around in two's complement.
+    Wrap,
+    /// Stop with an "integer overflow" runtime error.
+    Error,
+    /// Continue with an arbitrary-precision integer.
+    #[cfg(feature = "bigint")]
+    Promote,
+}
+
+impl Default for Overflow {
+    #[cfg(not(feature = "bigint"))]
+    fn default() -> Overflow {
+        Overflow::Wrap
+    }
+
+    #[cfg(feature = "bigint")]
+    fn default() -> Overflow {
+        Overflow::Promote
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Environment {
+    store: HashMap<String, Object>,
+    outer: Option<Rc<RefCell<Environment>>>,
+    overflow: Overflow,
+    /// Number of function calls being evaluated, shared by every scope
+    /// enclosed in the same root environment.
+    call_depth: Rc<Cell<usize>>,
+}
+
+impl Environment {
+    pub fn new() -> Environment {
+        Environment::default()
+    }
+
+    pub fn with_overflow(overflow: Overflow) -> Environment {
+        Environment { overflow, ..Environment::default() }
+    }
+
+    /// Creates a scope whose lookups fall back to `outer`, e.g. for a function
+    /// call. The overflow mode and call depth are inherited from `outer`.
+    pub fn new_enclosed(outer: Rc<RefCell<Environment>>) -> Environment {
+        let (overflow, call_depth) = {
+            let outer = outer.borrow();
+            (outer.overflow, Rc::clone(&outer.call_depth))
+        };
+        Environment { store: HashMap::new(), outer: Some(outer), overflow, call_depth }
+    }
+
+    pub fn overflow(&self) -> Overflow {
+        self.overflow
+    }
+
+    pub fn call_depth(&self) -> &Cell<usize> {
+        &self.call_depth
+    }
+
+    pub fn get(&self, name: &str) -> Option<Object> {
+        match self.store.get(name) {
+            Some(value) => Some(value.clone()),
+            None => self.outer.as_ref().and_then(|outer| outer.borrow().get(name)),
+        }
+    }
+
+    pub fn set(&mut self, name: String, value: Object) {
+        self.store.insert(name, value);
+    }
+
+    /// Clears the bindings of a finished call's environment when the only
+    /// references to it come from functions bound in its own store that are
+    /// not held anywhere else. Such functions and the environment keep each
+    /// other alive, so without this every call that binds a closure would
+    /// leak its environment. Cycles through nested calls, arrays or hashes
+    /// are not detected and are still leaked.
+    pub fn release(env: Rc<RefCell<Environment>>) {
+        let mut self_references = 0;
+        for value in env.borrow().store.values() {
+            if let Object::Function(function) = value {
+                if Rc::ptr_eq(&function.env, &env) {
+                    if Rc::strong_count(function) > 1 {
+                        return;
+                    }
+                    self_references += 1;
+                }
+            }
+        }
+
+        if Rc::strong_count(&env) == self_references + 1 {
+            let store = std::mem::take(&mut env.borrow_mut().store);
+            drop(store);
+        }
+    }
+}
@@ -0,0 +1,464 @@
+#[cfg(test)]
+mod mod_test;
+#[cfg(feature = "bigint")]
+mod bigint;
+pub mod builtins;
+pub mod environment;
+pub mod object;
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use crate::ast;
+pub use environment::{Environment, Overflow};
+pub use object::{Function, HashKey, Object};
+
+/// Deepest nesting of function calls before evaluation stops with an error
+/// instead of overflowing the native stack.
+const MAX_CALL_DEPTH: usize = 1000;
+
+pub fn eval_program(program: &ast::Program, env: &Rc<RefCell<Environment>>) -> Object {
+    let mut result = Object::Null;
+
+    for statement in &program.statements {
+        result = eval_statement(statement, env);
+
+        match result {
+            Object::ReturnValue(value) => return *value,
+            Object::Error(_) => return result,
+            _ => {}
+        }
+    }
+
+    result
+}
+
+fn eval_statement(statement: &ast::Statement, env: &Rc<RefCell<Environment>>) -> Object {
+    match statement {
+        ast::Statement::LetStatement(stmt) => {
+            let value = eval_optional_expression(&stmt.value, env);
+            if value.is_error() {
+                return value;
+            }
+            env.borrow_mut().set(stmt.name.value.clone(), value);
+            Object::Null
+        }
+        ast::Statement::ReturnStatement(stmt) => {
+            let value = eval_optional_expression(&stmt.return_value, env);
+            if value.is_error() {
+                return value;
+            }
+            Object::ReturnValue(Box::new(value))
+        }
+        ast::Statement::ExpressionStatement(stmt) => eval_optional_expression(&stmt.expression, env),
+    }
+}
+
+fn eval_optional_expression(expression: &Option<ast::Expression>, env: &Rc<RefCell<Environment>>) -> Object {
+    match expression {
+        Some(expression) => eval_expression(expression, env),
+        None => Object::Null,
+    }
+}
+
+fn eval_expression(expression: &ast::Expression, env: &Rc<RefCell<Environment>>) -> Object {
+    match expression {
+        ast::Expression::Identifier(identifier) => eval_identifier(identifier, env),
+        ast::Expression::IntegerLiteral(literal) => Object::Integer(literal.value),
+        #[cfg(feature = "bigint")]
+        ast::Expression::BigIntegerLiteral(literal) => Object::BigInt(literal.value.clone()),
+        ast::Expression::FloatLiteral(literal) => Object::Float(literal.value),
+        ast::Expression::StringLiteral(literal) => Object::String(literal.value.clone()),
+        ast::Expression::Boolean(boolean) => Object::Boolean(boolean.value),
+        ast::Expression::PrefixExpression(prefix) => {
+            let right = eval_expression(&prefix.right, env);
+            if right.is_error() {
+                return right;
+            }
+            eval_prefix_expression(&prefix.operator, right, env.borrow().overflow())
+        }
+        ast::Expression::InfixExpression(infix) if infix.operator == "&&" || infix.operator == "||" => {
+            eval_logical_expression(infix, env)
+        }
+        ast::Expression::InfixExpression(infix) => {
+            let left = eval_expression(&infix.left, env);
+            if left.is_error() {
+                return left;
+            }
+            let right = eval_expression(&infix.right, env);
+            if right.is_error() {
+                return right;
+            }
+            eval_infix_expression(&infix.operator, left, right, env.borrow().overflow())
+        }
+        ast::Expression::IfExpression(if_expression) => eval_if_expression(if_expression, env),
+        ast::Expression::FunctionLiteral(function) => Object::Function(Rc::new(Function {
+            parameters: function.parameters.clone(),
+            body: Rc::clone(&function.body),
+            env: Rc::clone(env),
+        })),
+        ast::Expression::CallExpression(call) => {
+            let function = eval_expression(&call.function, env);
+            if function.is_error() {
+                return function;
+            }
+            let arguments = match eval_expressions(&call.arguments, env) {
+                Ok(arguments) => arguments,
+                Err(error) => return error,
+            };
+            apply_function(function, arguments)
+        }
+        ast::Expression::ArrayLiteral(array) => match eval_expressions(&array.elements, env) {
+            Ok(elements) => Object::Array(elements),
+            Err(error) => error,
+        },
+        ast::Expression::IndexExpression(index_expression) => {
+            let left = eval_expression(&index_expression.left, env);
+            if left.is_error() {
+                return left;
+            }
+            let index = eval_expression(&index_expression.index, env);
+            if index.is_error() {
+                return index;
+            }
+            eval_index_expression(left, index)
+        }
+        ast::Expression::HashLiteral(hash) => eval_hash_literal(hash, env),
+    }
+}
+
+fn eval_hash_literal(hash: &ast::HashLiteral, env: &Rc<RefCell<Environment>>) -> Object {
+    let mut pairs = BTreeMap::new();
+
+    for (key_expression, value_expression) in &hash.pairs {
+        let key = eval_expression(key_expression, env);
+        if key.is_error() {
+            return key;
+        }
+        let hash_key = match key.hash_key() {
+            Some(hash_key) => hash_key,
+            None => return Object::Error(format!("unusable as hash key: {}", key.type_name())),
+        };
+
+        let value = eval_expression(value_expression, env);
+        if value.is_error() {
+            return value;
+        }
+        pairs.insert(hash_key, value);
+    }
+
+    Object::Hash(pairs)
+}
+
+fn eval_index_expression(left: Object, index: Object) -> Object {
+    match (left, index) {
+        (Object::Array(elements), Object::Integer(index)) => {
+            usize::try_from(index).ok().and_then(|index| elements.get(index).cloned()).unwrap_or(Object::Null)
+        }
+        (Object::Array(_), index) => Object::Error(format!("array index must be INTEGER, got {}", index.type_name())),
+        (Object::Hash(pairs), key) => match key.hash_key() {
+            Some(hash_key) => pairs.get(&hash_key).cloned().unwrap_or(Object::Null),
+            None => Object::Error(format!("unusable as hash key: {}", key.type_name())),
+        },
+        (left, _) => Object::Error(format!("index operator not supported: {}", left.type_name())),
+    }
+}
+
+fn eval_expressions(expressions: &[ast::Expression], env: &Rc<RefCell<Environment>>) -> Result<Vec<Object>, Object> {
+    let mut result = Vec::with_capacity(expressions.len());
+
+    for expression in expressions {
+        let evaluated = eval_expression(expression, env);
+        if evaluated.is_error() {
+            return Err(evaluated);
+        }
+        result.push(evaluated);
+    }
+
+    Ok(result)
+}
+
+fn apply_function(function: Object, arguments: Vec<Object>) -> Object {
+    let function = match function {
+        Object::Function(function) => function,
+        Object::Builtin(builtin) => return (builtin.func)(arguments),
+        other => return Object::Error(format!("not a function: {}", other.type_name())),
+    };
+
+    if function.parameters.len() != arguments.len() {
+        return Object::Error(format!(
+            "wrong number of arguments: want={}, got={}",
+            function.parameters.len(),
+            arguments.len()
+        ));
+    }
+
+    let mut extended = Environment::new_enclosed(Rc::clone(&function.env));
+    let depth = extended.call_depth().get();
+    if depth >= MAX_CALL_DEPTH {
+        return Object::Error(String::from("maximum call depth exceeded"));
+    }
+    for (parameter, argument) in function.parameters.iter().zip(arguments) {
+        extended.set(parameter.value.clone(), argument);
+    }
+
+    let extended = Rc::new(RefCell::new(extended));
+    extended.borrow().call_depth().set(depth + 1);
+    let result = eval_block_statement(&function.body, &extended);
+    extended.borrow().call_depth().set(depth);
+    Environment::release(extended);
+
+    match result {
+        Object::ReturnValue(value) => *value,
+        result => result,
+    }
+}
+
+fn eval_block_statement(block: &ast::BlockStatement, env: &Rc<RefCell<Environment>>) -> Object {
+    let mut result = Object::Null;
+
+    for statement in &block.statements {
+        result = eval_statement(statement, env);
+
+        if matches!(result, Object::ReturnValue(_) | Object::Error(_)) {
+            return result;
+        }
+    }
+
+    result
+}
+
+fn eval_if_expression(if_expression: &ast::IfExpression, env: &Rc<RefCell<Environment>>) -> Object {
+    let condition = eval_expression(&if_expression.condition, env);
+    if condition.is_error() {
+        return condition;
+    }
+
+    if condition.is_truthy() {
+        eval_block_statement(&if_expression.consequence, env)
+    } else if let Some(alternative) = &if_expression.alternative {
+        eval_block_statement(alternative, env)
+    } else {
+        Object::Null
+    }
+}
+
+fn eval_identifier(identifier: &ast::Identifier, env: &Rc<RefCell<Environment>>) -> Object {
+    if let Some(value) = env.borrow().get(&identifier.value) {
+        return value;
+    }
+    match builtins::lookup(&identifier.value) {
+        Some(builtin) => builtin,
+        None => Object::Error(format!("identifier not found: {}", identifier.value)),
+    }
+}
+
+/// `&&` and `||` only evaluate their right operand when the left one does
+/// not already decide the result, and always produce a Boolean.
+fn eval_logical_expression(infix: &ast::InfixExpression, env: &Rc<RefCell<Environment>>) -> Object {
+    let left = eval_expression(&infix.left, env);
+    if left.is_error() {
+        return left;
+    }
+
+    match (infix.operator.as_str(), left.is_truthy()) {
+        ("&&", false) => return Object::Boolean(false),
+        ("||", true) => return Object::Boolean(true),
+        _ => {}
+    }
+
+    let right = eval_expression(&infix.right, env);
+    if right.is_error() {
+        return right;
+    }
+    Object::Boolean(right.is_truthy())
+}
+
+fn eval_prefix_expression(operator: &str, right: Object, overflow: Overflow) -> Object {
+    match (operator, right) {
+        ("!", right) => Object::Boolean(!right.is_truthy()),
+        ("-", Object::Integer(value)) => match (value.overflowing_neg(), overflow) {
+            ((negated, false), _) | ((negated, true), Overflow::Wrap) => Object::Integer(negated),
+            (_, Overflow::Error) => Object::Error(format!("integer overflow: -({})", value)),
+            #[cfg(feature = "bigint")]
+            (_, Overflow::Promote) => bigint::eval_prefix_expression(operator, value.into()),
+        },
+        ("~", Object::Integer(value)) => Object::Integer(!value),
+        #[cfg(feature = "bigint")]
+        (operator @ ("-" | "~"), Object::BigInt(value)) => bigint::eval_prefix_expression(operator, value),
+        ("-", Object::Float(value)) => Object::Float(-value),
+        (operator, right) => Object::Error(format!("unknown operator: {}{}", operator, right.type_name())),
+    }
+}
+
+fn eval_infix_expression(operator: &str, left: Object, right: Object, overflow: Overflow) -> Object {
+    // Promoted operands are reported with their own types, e.g. `INTEGER & FLOAT`.
+    let (left_type, right_type) = (left.type_name(), right.type_name());
+    let unknown_operator = || Object::Error(format!("unknown operator: {} {} {}", left_type, operator, right_type));
+
+    match (left, right) {
+        (Object::Integer(left), Object::Integer(right)) => eval_integer_infix_expression(operator, left, right, overflow),
+        #[cfg(feature = "bigint")]
+        (Object::BigInt(left), Object::BigInt(right)) => bigint::eval_infix_expression(operator, left, right),
+        #[cfg(feature = "bigint")]
+        (Object::BigInt(left), Object::Integer(right)) => bigint::eval_infix_expression(operator, left, right.into()),
+        #[cfg(feature = "bigint")]
+        (Object::Integer(left), Object::BigInt(right)) => bigint::eval_infix_expression(operator, left.into(), right),
+        #[cfg(feature = "bigint")]
+        (Object::BigInt(left), Object::Float(right)) => {
+            eval_float_infix_expression(operator, bigint::to_f64(&left), right).unwrap_or_else(unknown_operator)
+        }
+        #[cfg(feature = "bigint")]
+        (Object::Float(left), Object::BigInt(right)) => {
+            eval_float_infix_expression(operator, left, bigint::to_f64(&right)).unwrap_or_else(unknown_operator)
+        }
+        // An integer meeting a float is promoted, so `1 / 2.0` is 0.5 while
+        // `1 / 2` stays integer division.
+        (Object::Float(left), Object::Float(right)) => {
+            eval_float_infix_expression(operator, left, right).unwrap_or_else(unknown_operator)
+        }
+        (Object::Integer(left), Object::Float(right)) => {
+            eval_float_infix_expression(operator, left as f64, right).unwrap_or_else(unknown_operator)
+        }
+        (Object::Float(left), Object::Integer(right)) => {
+            eval_float_infix_expression(operator, left, right as f64).unwrap_or_else(unknown_operator)
+        }
+        (Object::String(left), Object::String(right)) => match operator {
+            "+" => Object::String(left + &right),
+            "==" => Object::Boolean(left == right),
+            "!=" => Object::Boolean(left != right),
+            _ => Object::Error(format!("unknown operator: STRING {} STRING", operator)),
+        },
+        (Object::Boolean(left), Object::Boolean(right)) => match operator {
+            "==" => Object::Boolean(left == right),
+            "!=" => Object::Boolean(left != right),
+            _ => Object::Error(format!("unknown operator: BOOLEAN {} BOOLEAN", operator)),
+        },
+        (left, right) if left.type_name() != right.type_name() => {
+            Object::Error(format!("type mismatch: {} {} {}", left.type_name(), operator, right.type_name()))
+        }
+        _ => unknown_operator(),
+    }
+}
+
+/// Exponentiation by squaring, returning the wrapped result and whether it
+/// overflowed. Unlike `i64::overflowing_pow` the exponent is not limited to
+/// `u32`.
+fn overflowing_pow(mut base: i64, mut exponent: u64) -> (i64, bool) {
+    let (mut result, mut overflowed) = (1i64, false);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            let (value, overflow) = result.overflowing_mul(base);
+            (result, overflowed) = (value, overflowed || overflow);
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            let (value, overflow) = base.overflowing_mul(base);
+            (base, overflowed) = (value, overflowed || overflow);
+        }
+    }
+    (result, overflowed)
+}
+
+/// Shifts left, treating any bit shifted out (or a change of sign) as an
+/// overflow, like a multiplication by a power of two.
+fn overflowing_shl(left: i64, shift: u64) -> (i64, bool) {
+    match u32::try_from(shift).ok().and_then(|shift| left.checked_shl(shift)) {
+        Some(value) => (value, value >> shift != left),
+        None => (0, left != 0),
+    }
+}
+
+fn eval_integer_infix_expression(operator: &str, left: i64, right: i64, overflow: Overflow) -> Object {
+    let (value, overflowed) = match operator {
+        "+" => left.overflowing_add(right),
+        "-" => left.overflowing_sub(right),
+        "*" => left.overflowing_mul(right),
+        "/" => {
+            if right == 0 {
+                return Object::Error(String::from("division by zero"));
+            }
+            left.overflowing_div(right)
+        }
+        // The remainder takes the sign of the dividend, as in Rust and Go.
+        "%" => {
+            if right == 0 {
+                return Object::Error(String::from("modulo by zero"));
+            }
+            // `i64::MIN % -1` overflows in Rust, but the remainder is 0.
+            if right == -1 {
+                return Object::Integer(0);
+            }
+            left.overflowing_rem(right)
+        }
+        "**" => {
+            if right < 0 {
+                return Object::Error(format!("negative exponent: {}", right));
+            }
+            overflowing_pow(left, right as u64)
+        }
+        "<<" => {
+            if right < 0 {
+                return Object::Error(format!("negative shift amount: {}", right));
+            }
+            overflowing_shl(left, right as u64)
+        }
+        // Shifting right by 64 or more leaves only the sign.
+        ">>" => {
+            if right < 0 {
+                return Object::Error(format!("negative shift amount: {}", right));
+            }
+            let shift = u32::try_from(right).unwrap_or(u32::MAX);
+            return Object::Integer(left.checked_shr(shift).unwrap_or(if left < 0 { -1 } else { 0 }));
+        }
+        "&" => return Object::Integer(left & right),
+        "|" => return Object::Integer(left | right),
+        "^" => return Object::Integer(left ^ right),
+        "<" => return Object::Boolean(left < right),
+        ">" => return Object::Boolean(left > right),
+        "<=" => return Object::Boolean(left <= right),
+        ">=" => return Object::Boolean(left >= right),
+        "==" => return Object::Boolean(left == right),
+        "!=" => return Object::Boolean(left != right),
+        _ => return Object::Error(format!("unknown operator: INTEGER {} INTEGER", operator)),
+    };
+
+    match (overflowed, overflow) {
+        (false, _) | (true, Overflow::Wrap) => Object::Integer(value),
+        (true, Overflow::Error) => Object::Error(format!("integer overflow: {} {} {}", left, operator, right)),
+        #[cfg(feature = "bigint")]
+        (true, Overflow::Promote) => bigint::eval_infix_expression(operator, left.into(), right.into()),
+    }
+}
+
+/// Returns None for an operator floats do not support, leaving the error to
+/// the caller, which knows whether either operand was an integer.
+fn eval_float_infix_expression(operator: &str, left: f64, right: f64) -> Option<Object> {
+    let result = match operator {
+        "+" => Object::Float(left + right),
+        "-" => Object::Float(left - right),
+        "*" => Object::Float(left * right),
+        "/" => {
+            if right == 0.0 {
+                return Some(Object::Error(String::from("division by zero")));
+            }
+            Object::Float(left / right)
+        }
+        "%" => {
+            if right == 0.0 {
+                return Some(Object::Error(String::from("modulo by zero")));
+            }
+            Object::Float(left % right)
+        }
+        "**" => Object::Float(left.powf(right)),
+        "<" => Object::Boolean(left < right),
+        ">" => Object::Boolean(left > right),
+        "<=" => Object::Boolean(left <= right),
+        ">=" => Object::Boolean(left >= right),
+        "==" => Object::Boolean(left == right),
+        "!=" => Object::Boolean(left != right),
+        _ => return None,
+    };
+    Some(result)
+}
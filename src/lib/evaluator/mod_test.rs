@@ -0,0 +1,882 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::evaluator::{eval_program, Environment, HashKey, Object, Overflow};
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+fn test_eval_with_env(input: &str, env: &Rc<RefCell<Environment>>) -> Object {
+    let lex = Lexer::new(input);
+    let mut parser = Parser::new(lex);
+    let program = parser.parse_program().unwrap_or_else(|| panic!("parse_program() returned None"));
+    if !parser.errors().is_empty() {
+        panic!("parser errors for {:?}: {:?}", input, parser.errors());
+    }
+    eval_program(&program, env)
+}
+
+fn test_eval(input: &str) -> Object {
+    test_eval_with_env(input, &Rc::new(RefCell::new(Environment::new())))
+}
+
+#[test]
+fn test_eval_integer_expression() {
+    let tests = [
+        ("5", 5),
+        ("10", 10),
+        ("-5", -5),
+        ("-10", -10),
+        ("5 + 5 + 5 + 5 - 10", 10),
+        ("2 * 2 * 2 * 2 * 2", 32),
+        ("-50 + 100 + -50", 0),
+        ("(5 + 10 * 2 + 15 / 3) * 2 + -10", 50),
+        ("2 * (5 + 10)", 30),
+        ("5 * 2 + 10", 20),
+        ("5 + 2 * 10", 25),
+        ("20 + 2 * -10", 0),
+        ("50 / 2 * 2 + 10", 60),
+        ("3 * 3 * 3 + 10", 37),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != Object::Integer(expected) {
+            panic!("{}: object is not Integer({}). got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_eval_boolean_expression() {
+    let tests = [
+        ("true", true),
+        ("false", false),
+        ("1 < 2", true),
+        ("1 > 2", false),
+        ("1 < 1", false),
+        ("1 == 1", true),
+        ("1 != 1", false),
+        ("1 == 2", false),
+        ("1 != 2", true),
+        ("1 < 2 == 2 > 1", true),
+        ("1 < 2 != 2 > 1", false),
+        ("true == true", true),
+        ("false == false", true),
+        ("true == false", false),
+        ("true != false", true),
+        ("(1 < 2) == true", true),
+        ("(1 > 2) == true", false),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != Object::Boolean(expected) {
+            panic!("{}: object is not Boolean({}). got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_bang_operator() {
+    let tests = [
+        ("!true", false),
+        ("!false", true),
+        ("!5", false),
+        ("!!true", true),
+        ("!!false", false),
+        ("!!5", true),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != Object::Boolean(expected) {
+            panic!("{}: object is not Boolean({}). got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_error_handling() {
+    let tests = [
+        ("5 + true;", "type mismatch: INTEGER + BOOLEAN"),
+        ("5 + true; 5;", "type mismatch: INTEGER + BOOLEAN"),
+        ("-true", "unknown operator: -BOOLEAN"),
+        ("true + false;", "unknown operator: BOOLEAN + BOOLEAN"),
+        ("5; true + false; 5", "unknown operator: BOOLEAN + BOOLEAN"),
+        ("1 < 2 == 3", "type mismatch: BOOLEAN == INTEGER"),
+        ("1 < 2 < 3; 5", "type mismatch: BOOLEAN < INTEGER"),
+        ("1 < 2 > 1 < 2", "type mismatch: BOOLEAN > INTEGER"),
+        ("foobar", "identifier not found: foobar"),
+        ("10 / 0", "division by zero"),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        match evaluated {
+            Object::Error(message) => {
+                if message != expected {
+                    panic!("wrong error message. expected={:?}, got={:?}", expected, message);
+                }
+            }
+            _ => panic!("no error object returned for {}. got={:?}", input, evaluated),
+        }
+    }
+}
+
+#[test]
+fn test_identifiers_resolve_from_environment() {
+    let env = Rc::new(RefCell::new(Environment::new()));
+    env.borrow_mut().set(String::from("a"), Object::Integer(5));
+    env.borrow_mut().set(String::from("b"), Object::Integer(10));
+
+    let evaluated = test_eval_with_env("a * b + a", &env);
+    if evaluated != Object::Integer(55) {
+        panic!("object is not Integer(55). got={:?}", evaluated);
+    }
+}
+
+#[test]
+fn test_return_statements() {
+    let tests = [
+        ("return 10;", 10),
+        ("return 10; 9;", 10),
+        ("return 2 * 5; 9;", 10),
+        ("9; return 2 * 5; 9;", 10),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != Object::Integer(expected) {
+            panic!("{}: object is not Integer({}). got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_let_statements() {
+    let tests = [
+        ("let a = 5; a;", 5),
+        ("let a = 5 * 5; a;", 25),
+        ("let a = 5; let b = a; b;", 5),
+        ("let a = 5; let b = a; let c = a + b + 5; c;", 15),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != Object::Integer(expected) {
+            panic!("{}: object is not Integer({}). got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_if_else_expressions() {
+    let tests = [
+        ("if (true) { 10 }", Object::Integer(10)),
+        ("if (false) { 10 }", Object::Null),
+        ("if (1) { 10 }", Object::Integer(10)),
+        ("if (1 < 2) { 10 }", Object::Integer(10)),
+        ("if (1 > 2) { 10 }", Object::Null),
+        ("if (1 > 2) { 10 } else { 20 }", Object::Integer(20)),
+        ("if (1 < 2) { 10 } else { 20 }", Object::Integer(10)),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != expected {
+            panic!("{}: expected {:?}. got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_nested_return_statements() {
+    let tests = [
+        ("if (10 > 1) { return 10; }", 10),
+        ("if (10 > 1) { if (10 > 1) { return 10; } return 1; }", 10),
+        ("let a = 1; if (a > 0) { if (a > 0) { return a * 7; } return 1; } 3", 7),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != Object::Integer(expected) {
+            panic!("{}: object is not Integer({}). got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_errors_stop_block_evaluation() {
+    let input = "if (10 > 1) { if (10 > 1) { return true + false; } return 1; }";
+
+    let evaluated = test_eval(input);
+    if evaluated != Object::Error(String::from("unknown operator: BOOLEAN + BOOLEAN")) {
+        panic!("expected unknown operator error. got={:?}", evaluated);
+    }
+}
+
+#[test]
+fn test_function_object() {
+    match test_eval("fn(x) { x + 2; };") {
+        Object::Function(function) => {
+            let parameters: Vec<&str> = function.parameters.iter().map(|parameter| parameter.value.as_str()).collect();
+            if parameters != ["x"] {
+                panic!("function has wrong parameters. got={:?}", parameters);
+            }
+            if function.to_string() != "fn(x) { (x + 2) }" {
+                panic!("function.to_string() wrong. got={}", function);
+            }
+        }
+        evaluated => panic!("object is not Function. got={:?}", evaluated),
+    }
+}
+
+#[test]
+fn test_function_application() {
+    let tests = [
+        ("let identity = fn(x) { x; }; identity(5);", 5),
+        ("let identity = fn(x) { return x; }; identity(5);", 5),
+        ("let double = fn(x) { x * 2; }; double(5);", 10),
+        ("let add = fn(x, y) { x + y; }; add(5, 5);", 10),
+        ("let add = fn(x, y) { x + y; }; add(5 + 5, add(5, 5));", 20),
+        ("fn(x) { x; }(5)", 5),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != Object::Integer(expected) {
+            panic!("{}: object is not Integer({}). got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_function_application_errors() {
+    let tests = [
+        ("let add = fn(x, y) { x + y; }; add(1);", "wrong number of arguments: want=2, got=1"),
+        ("5(1)", "not a function: INTEGER"),
+        ("let f = fn(x) { x }; f(missing)", "identifier not found: missing"),
+        ("let f = fn() { y }; f()", "identifier not found: y"),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != Object::Error(String::from(expected)) {
+            panic!("{}: expected error {:?}. got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_closures() {
+    let tests = [
+        ("let adder = fn(x) { fn(y) { x + y } }; adder(2)(3)", 5),
+        ("let newAdder = fn(x) { fn(y) { x + y }; }; let addTwo = newAdder(2); addTwo(2);", 4),
+        // captured bindings are not affected by later shadowing in the caller
+        ("let x = 10; let f = fn() { x }; let g = fn(x) { f() }; g(99)", 10),
+        ("let make = fn(a) { let b = a * 2; fn(c) { a + b + c } }; let h = make(1); let i = make(10); h(0) + i(0)", 33),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != Object::Integer(expected) {
+            panic!("{}: object is not Integer({}). got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_currying() {
+    let input = "
+let curry3 = fn(f) { fn(a) { fn(b) { fn(c) { f(a, b, c) } } } };
+let volume = fn(w, h, d) { w * h * d };
+let widthTwo = curry3(volume)(2);
+widthTwo(3)(4) + widthTwo(1)(1)";
+
+    let evaluated = test_eval(input);
+    if evaluated != Object::Integer(26) {
+        panic!("object is not Integer(26). got={:?}", evaluated);
+    }
+}
+
+#[test]
+fn test_higher_order_functions() {
+    let tests = [
+        ("let apply = fn(f, x) { f(x) }; apply(fn(n) { n * n }, 7)", 49),
+        ("let twice = fn(f) { fn(x) { f(f(x)) } }; let inc = fn(x) { x + 1 }; twice(twice(inc))(0)", 4),
+        ("let compose = fn(f, g) { fn(x) { g(f(x)) } }; compose(fn(x) { x + 1 }, fn(x) { x * 10 })(4)", 50),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != Object::Integer(expected) {
+            panic!("{}: object is not Integer({}). got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_counter() {
+    let input = "
+let counter = fn(x) {
+    if (x > 100) {
+        return x;
+    } else {
+        let foobar = 9999;
+        counter(x + 1);
+    }
+};
+counter(0);";
+
+    let evaluated = test_eval(input);
+    if evaluated != Object::Integer(101) {
+        panic!("object is not Integer(101). got={:?}", evaluated);
+    }
+}
+
+#[test]
+fn test_recursion_through_let_bound_names() {
+    let tests = [
+        ("let fib = fn(n) { if (n < 2) { n } else { fib(n - 1) + fib(n - 2) } }; fib(15)", 610),
+        ("let fact = fn(n) { if (n == 0) { 1 } else { n * fact(n - 1) } }; fact(10)", 3628800),
+        (
+            "let isEven = fn(n) { if (n == 0) { true } else { isOdd(n - 1) } };
+             let isOdd = fn(n) { if (n == 0) { false } else { isEven(n - 1) } };
+             if (isEven(10)) { 1 } else { 0 }",
+            1,
+        ),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != Object::Integer(expected) {
+            panic!("{}: object is not Integer({}). got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_call_depth_limit() {
+    // Reaching the limit takes more stack than a test thread has in an
+    // unoptimized build.
+    let evaluated = std::thread::Builder::new()
+        .stack_size(256 * 1024 * 1024)
+        .spawn(|| {
+            let env = Rc::new(RefCell::new(Environment::new()));
+            let inputs = [
+                "let c = fn(x) { if (x == 0) { 0 } else { c(x - 1) } }; c(999)",
+                "c(1000)",
+                "c(20000)",
+                "c(10)",
+            ];
+            inputs.map(|input| test_eval_with_env(input, &env).to_string())
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+
+    let expected = ["0", "ERROR: maximum call depth exceeded", "ERROR: maximum call depth exceeded", "0"];
+    if evaluated != expected {
+        panic!("wrong results near the call depth limit. expected={:?}, got={:?}", expected, evaluated);
+    }
+}
+
+#[test]
+fn test_call_environments_with_closures_are_released() {
+    let input = "
+let f = fn() { 1 };
+let outer = fn(g) { let inner = fn(x) { x }; inner(g) };
+outer(f);
+outer(f);";
+
+    let env = Rc::new(RefCell::new(Environment::new()));
+    test_eval_with_env(input, &env);
+    let f = match env.borrow().get("f") {
+        Some(Object::Function(f)) => f,
+        other => panic!("f is not a Function. got={:?}", other),
+    };
+    // Held only by the global environment and `f` above.
+    if Rc::strong_count(&f) != 2 {
+        panic!("call environments still hold f. strong_count={}", Rc::strong_count(&f));
+    }
+
+    let evaluated = test_eval("let make = fn() { let inner = fn(x) { x * 2 }; inner }; make()(21)");
+    if evaluated != Object::Integer(42) {
+        panic!("returned closure is broken. got={:?}", evaluated);
+    }
+}
+
+#[test]
+fn test_unicode_identifiers_and_strings() {
+    let tests = [
+        ("let user2 = 5; let größe = user2 * 2; größe", Object::Integer(10)),
+        ("let 変数 = fn(名前) { \"こんにちは、\" + 名前 }; 変数(\"世界\")", Object::String(String::from("こんにちは、世界"))),
+        ("let party = \"🎉🐒\"; len(party)", Object::Integer(2)),
+        ("let _x1 = \"a🐒b\"; _x1 + \"!\"", Object::String(String::from("a🐒b!"))),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != expected {
+            panic!("{}: expected {:?}. got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_string_literal() {
+    let evaluated = test_eval(r#""Hello World!""#);
+    if evaluated != Object::String(String::from("Hello World!")) {
+        panic!("object is not String(\"Hello World!\"). got={:?}", evaluated);
+    }
+}
+
+#[test]
+fn test_string_concatenation() {
+    let tests = [
+        (r#""Hello" + " " + "World!""#, Object::String(String::from("Hello World!"))),
+        (r#"let greet = fn(name) { "Hi, " + name + "\n" }; greet("Ann")"#, Object::String(String::from("Hi, Ann\n"))),
+        (r#""a" == "a""#, Object::Boolean(true)),
+        (r#""a" != "b""#, Object::Boolean(true)),
+        (r#""Hello" - "World""#, Object::Error(String::from("unknown operator: STRING - STRING"))),
+        (r#""Hello" + 1"#, Object::Error(String::from("type mismatch: STRING + INTEGER"))),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != expected {
+            panic!("{}: expected {:?}. got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_comments_are_ignored() {
+    let input = "
+        // doubles its argument
+        let double = fn(x) { x * 2 /* * 3 */ };
+        /* the /* nested */ block is skipped */
+        double(10) / 4 // => 5
+    ";
+    let evaluated = test_eval(input);
+    if evaluated != Object::Integer(5) {
+        panic!("expected 5. got={:?}", evaluated);
+    }
+}
+
+#[test]
+fn test_array_literals() {
+    let evaluated = test_eval("[1, 2 * 2, 3 + 3]");
+    if evaluated != Object::Array(vec![Object::Integer(1), Object::Integer(4), Object::Integer(6)]) {
+        panic!("object is not [1, 4, 6]. got={:?}", evaluated);
+    }
+    if evaluated.to_string() != "[1, 4, 6]" {
+        panic!("array display wrong. got={}", evaluated);
+    }
+}
+
+#[test]
+fn test_array_index_expressions() {
+    let tests = [
+        ("[1, 2, 3][0]", Object::Integer(1)),
+        ("[1, 2, 3][1]", Object::Integer(2)),
+        ("[1, 2, 3][2]", Object::Integer(3)),
+        ("let i = 0; [1][i];", Object::Integer(1)),
+        ("[1, 2, 3][1 + 1];", Object::Integer(3)),
+        ("let myArray = [1, 2, 3]; myArray[2];", Object::Integer(3)),
+        ("let myArray = [1, 2, 3]; myArray[0] + myArray[1] + myArray[2];", Object::Integer(6)),
+        ("let myArray = [1, 2, 3]; let i = myArray[0]; myArray[i]", Object::Integer(2)),
+        ("[1, 2, 3][3]", Object::Null),
+        ("[1, 2, 3][-1]", Object::Null),
+        ("[1, 2, 3][true]", Object::Error(String::from("array index must be INTEGER, got BOOLEAN"))),
+        ("5[0]", Object::Error(String::from("index operator not supported: INTEGER"))),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != expected {
+            panic!("{}: expected {:?}. got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_builtin_functions() {
+    let array = |values: &[i64]| Object::Array(values.iter().map(|value| Object::Integer(*value)).collect());
+    let error = |message: &str| Object::Error(String::from(message));
+    let tests = [
+        (r#"len("")"#, Object::Integer(0)),
+        (r#"len("four")"#, Object::Integer(4)),
+        (r#"len("hello world")"#, Object::Integer(11)),
+        (r#"len("héllo")"#, Object::Integer(5)),
+        ("len(1)", error("argument to `len` not supported, got INTEGER")),
+        (r#"len("one", "two")"#, error("wrong number of arguments. got=2, want=1")),
+        ("len([1, 2, 3])", Object::Integer(3)),
+        ("len([])", Object::Integer(0)),
+        ("first([1, 2, 3])", Object::Integer(1)),
+        ("first([])", Object::Null),
+        ("first(1)", error("argument to `first` must be ARRAY, got INTEGER")),
+        ("last([1, 2, 3])", Object::Integer(3)),
+        ("last([])", Object::Null),
+        ("last(1)", error("argument to `last` must be ARRAY, got INTEGER")),
+        ("rest([1, 2, 3])", array(&[2, 3])),
+        ("rest([])", Object::Null),
+        ("push([], 1)", array(&[1])),
+        ("let a = [1]; let b = push(a, 2); len(a) + len(b)", Object::Integer(3)),
+        ("push(1, 1)", error("argument to `push` must be ARRAY, got INTEGER")),
+        ("push([1])", error("wrong number of arguments. got=1, want=2")),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != expected {
+            panic!("{}: expected {:?}. got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_array_functions_in_monkey() {
+    let input = "
+let map = fn(arr, f) {
+    let iter = fn(arr, accumulated) {
+        if (len(arr) == 0) {
+            accumulated
+        } else {
+            iter(rest(arr), push(accumulated, f(first(arr))));
+        }
+    };
+    iter(arr, []);
+};
+let reduce = fn(arr, initial, f) {
+    let iter = fn(arr, result) {
+        if (len(arr) == 0) {
+            result
+        } else {
+            iter(rest(arr), f(result, first(arr)));
+        }
+    };
+    iter(arr, initial);
+};
+let doubled = map([1, 2, 3, 4], fn(x) { x * 2 });
+reduce(doubled, 0, fn(acc, x) { acc + x })";
+
+    let evaluated = test_eval(input);
+    if evaluated != Object::Integer(20) {
+        panic!("object is not Integer(20). got={:?}", evaluated);
+    }
+}
+
+#[test]
+fn test_hash_literals() {
+    let input = r#"let two = "two";
+{
+    "one": 10 - 9,
+    two: 1 + 1,
+    "thr" + "ee": 6 / 2,
+    4: 4,
+    true: 5,
+    false: 6
+}"#;
+
+    let evaluated = test_eval(input);
+    let pairs = match evaluated {
+        Object::Hash(pairs) => pairs,
+        other => panic!("eval didn't return Hash. got={:?}", other),
+    };
+
+    let expected = [
+        (HashKey::String(String::from("one")), 1),
+        (HashKey::String(String::from("two")), 2),
+        (HashKey::String(String::from("three")), 3),
+        (HashKey::Integer(4), 4),
+        (HashKey::Boolean(true), 5),
+        (HashKey::Boolean(false), 6),
+    ];
+    if pairs.len() != expected.len() {
+        panic!("hash has wrong number of pairs. got={}", pairs.len());
+    }
+    for (key, value) in expected {
+        if pairs.get(&key) != Some(&Object::Integer(value)) {
+            panic!("no pair {:?}: {} in hash. got={:?}", key, value, pairs.get(&key));
+        }
+    }
+}
+
+#[test]
+fn test_hash_index_expressions() {
+    let tests = [
+        (r#"{"foo": 5}["foo"]"#, Object::Integer(5)),
+        (r#"{"foo": 5}["bar"]"#, Object::Null),
+        (r#"let key = "foo"; {"foo": 5}[key]"#, Object::Integer(5)),
+        (r#"{}["foo"]"#, Object::Null),
+        ("{5: 5}[5]", Object::Integer(5)),
+        ("{true: 5}[true]", Object::Integer(5)),
+        ("{false: 5}[false]", Object::Integer(5)),
+        (r#"{"name": "x", 1: true}[1]"#, Object::Boolean(true)),
+        (r#"{"name": "Monkey"}[fn(x) { x }];"#, Object::Error(String::from("unusable as hash key: FUNCTION"))),
+        (r#"{fn(x) { x }: "Monkey"}"#, Object::Error(String::from("unusable as hash key: FUNCTION"))),
+        ("{[1]: 1}", Object::Error(String::from("unusable as hash key: ARRAY"))),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != expected {
+            panic!("{}: expected {:?}. got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_hash_display() {
+    let evaluated = test_eval(r#"{"b": [1, 2], 1: true, "a": "x"}"#);
+    if evaluated.to_string() != "{1: true, a: x, b: [1, 2]}" {
+        panic!("hash display wrong. got={}", evaluated);
+    }
+}
+
+#[test]
+fn test_comparison_and_logical_operators() {
+    let tests = [
+        ("1 <= 2", true),
+        ("2 <= 2", true),
+        ("3 <= 2", false),
+        ("1 >= 2", false),
+        ("2 >= 2", true),
+        ("true && true", true),
+        ("true && false", false),
+        ("false || true", true),
+        ("false || false", false),
+        ("1 && 0", true),
+        ("1 < 2 && 2 < 3", true),
+        ("1 > 2 || 2 >= 3", false),
+        ("false && true || true", true),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != Object::Boolean(expected) {
+            panic!("{}: object is not Boolean({}). got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_arithmetic_and_bitwise_operators() {
+    let tests = [
+        ("7 % 3", 1),
+        ("-7 % 3", -1),
+        ("7 % -3", 1),
+        ("2 ** 10", 1024),
+        ("2 ** 3 ** 2", 512),
+        ("-2 ** 2", -4),
+        ("(-2) ** 3", -8),
+        ("5 ** 0", 1),
+        ("12 & 10", 8),
+        ("12 | 10", 14),
+        ("12 ^ 10", 6),
+        ("~0", -1),
+        ("~5", -6),
+        ("1 << 4", 16),
+        ("256 >> 4", 16),
+        ("-16 >> 2", -4),
+        ("-1 >> 100", -1),
+        ("let hash = 31 * 7 + 3; hash % 16", 12),
+        ("0xFF & 0b1010", 10),
+        ("0o755 >> 6", 7),
+        ("1_000_000 / 1_000", 1000),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != Object::Integer(expected) {
+            panic!("{}: object is not Integer({}). got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_wrapping_integer_overflow() {
+    let tests = [
+        ("9223372036854775807 + 1", i64::MIN),
+        ("-9223372036854775807 - 2", i64::MAX),
+        ("4611686018427387904 * 2", i64::MIN),
+        ("let min = -9223372036854775807 - 1; min / -1", i64::MIN),
+        ("let min = -9223372036854775807 - 1; -min", i64::MIN),
+        ("2 ** 64", 0),
+        ("3 ** 5000000000", -3621029483209119743),
+        ("1 << 64", 0),
+        ("3 << 62", -4611686018427387904),
+    ];
+
+    for (input, expected) in tests {
+        let env = Rc::new(RefCell::new(Environment::with_overflow(Overflow::Wrap)));
+        let evaluated = test_eval_with_env(input, &env);
+        if evaluated != Object::Integer(expected) {
+            panic!("{}: object is not Integer({}). got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_checked_integer_overflow() {
+    let tests = [
+        ("9223372036854775807 + 1", Object::Error(String::from("integer overflow: 9223372036854775807 + 1"))),
+        ("-9223372036854775807 - 2", Object::Error(String::from("integer overflow: -9223372036854775807 - 2"))),
+        ("4611686018427387904 * 2", Object::Error(String::from("integer overflow: 4611686018427387904 * 2"))),
+        (
+            "let min = -9223372036854775807 - 1; min / -1",
+            Object::Error(String::from("integer overflow: -9223372036854775808 / -1")),
+        ),
+        ("let min = -9223372036854775807 - 1; min % -1", Object::Integer(0)),
+        ("let min = -9223372036854775807 - 1; -min", Object::Error(String::from("integer overflow: -(-9223372036854775808)"))),
+        ("2 ** 63", Object::Error(String::from("integer overflow: 2 ** 63"))),
+        ("1 << 63", Object::Error(String::from("integer overflow: 1 << 63"))),
+        ("(-2) ** 63", Object::Integer(i64::MIN)),
+        ("-1 << 63", Object::Integer(i64::MIN)),
+        ("1 ** 5000000000", Object::Integer(1)),
+        ("9223372036854775807 - 1 + 1", Object::Integer(i64::MAX)),
+        (
+            "let fact = fn(n) { if (n < 2) { 1 } else { n * fact(n - 1) } }; fact(21)",
+            Object::Error(String::from("integer overflow: 21 * 2432902008176640000")),
+        ),
+    ];
+
+    for (input, expected) in tests {
+        let env = Rc::new(RefCell::new(Environment::with_overflow(Overflow::Error)));
+        let evaluated = test_eval_with_env(input, &env);
+        if evaluated != expected {
+            panic!("{}: expected {:?}. got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[cfg(feature = "bigint")]
+#[test]
+fn test_big_integer_promotion() {
+    let tests = [
+        ("9223372036854775807 + 1", "9223372036854775808"),
+        ("let fact = fn(n) { if (n < 2) { 1 } else { n * fact(n - 1) } }; fact(25)", "15511210043330985984000000"),
+        ("2 ** 100", "1267650600228229401496703205376"),
+        ("1 << 70", "1180591620717411303424"),
+        ("-9223372036854775808", "-9223372036854775808"),
+        ("(2 ** 64) / (2 ** 60)", "16"),
+        ("(2 ** 64) - (2 ** 64) + 1", "1"),
+        ("-(2 ** 70) % 1000", "-424"),
+        ("~(2 ** 64)", "-18446744073709551617"),
+        ("(2 ** 65) >> 64", "2"),
+        ("0xFFFF_FFFF_FFFF_FFFF_FF & 0xFF", "255"),
+        ("(2 ** 64) > 9223372036854775807", "true"),
+        ("(2 ** 64) == 18446744073709551616", "true"),
+        ("(2 ** 64) * 0.5", "9.223372036854776e18"),
+        ("{18446744073709551616: 1}[2 ** 64]", "1"),
+        ("(2 ** 64) / 0", "ERROR: division by zero"),
+        ("2 ** (2 ** 40)", "ERROR: exponent too large: 1099511627776"),
+        ("3 ** 4000000000", "ERROR: exponent too large: 4000000000"),
+        ("(-1) ** 4000000001", "-1"),
+        ("1 << 40000000000", "ERROR: shift amount too large: 40000000000"),
+        ("0 << 40000000000", "0"),
+        ("(2 ** 64) >> 40000000000", "0"),
+        ("-(2 ** 64) >> 40000000000", "-1"),
+        ("let big = 1 << 1000000; big * big", "ERROR: integer result too large"),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input).to_string();
+        if evaluated != expected {
+            panic!("{}: expected {:?}. got={:?}", input, expected, evaluated);
+        }
+    }
+
+    if test_eval("(2 ** 64) / (2 ** 60)") != Object::Integer(16) {
+        panic!("small results are not narrowed back to Object::Integer");
+    }
+}
+
+#[test]
+fn test_arithmetic_and_bitwise_errors() {
+    let tests = [
+        ("1 / 0", "division by zero"),
+        ("1 % 0", "modulo by zero"),
+        ("2 ** -1", "negative exponent: -1"),
+        ("1 << -1", "negative shift amount: -1"),
+        ("1 >> -2", "negative shift amount: -2"),
+        ("true & false", "unknown operator: BOOLEAN & BOOLEAN"),
+        ("~true", "unknown operator: ~BOOLEAN"),
+        ("\"a\" % 2", "type mismatch: STRING % INTEGER"),
+        ("1 & 1.5", "unknown operator: INTEGER & FLOAT"),
+        ("2.5 << 1", "unknown operator: FLOAT << INTEGER"),
+        ("2.5 | 1.5", "unknown operator: FLOAT | FLOAT"),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != Object::Error(String::from(expected)) {
+            panic!("{}: expected error {:?}. got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_float_arithmetic_and_promotion() {
+    let tests = [
+        ("2.75", Object::Float(2.75)),
+        ("-.5", Object::Float(-0.5)),
+        ("1.5 + 2.25", Object::Float(3.75)),
+        ("1 / 2.0", Object::Float(0.5)),
+        ("7 / 2", Object::Integer(3)),
+        ("2 * 0.25 + 1", Object::Float(1.5)),
+        ("7.5 % 2", Object::Float(1.5)),
+        ("2.0 ** -1", Object::Float(0.5)),
+        ("4 ** 0.5", Object::Float(2.0)),
+        ("let total = 40; let done = 10; done / total * 100.0", Object::Float(0.0)),
+        ("let total = 40; let done = 10; done * 100.0 / total", Object::Float(25.0)),
+        ("1 == 1.0", Object::Boolean(true)),
+        ("0.1 + 0.2 == 0.3", Object::Boolean(false)),
+        ("2 < 2.5", Object::Boolean(true)),
+        ("2.5 >= 3", Object::Boolean(false)),
+        ("1.0 / 0", Object::Error(String::from("division by zero"))),
+        ("1.5 % 0.0", Object::Error(String::from("modulo by zero"))),
+        ("1.0 & 1", Object::Error(String::from("unknown operator: FLOAT & INTEGER"))),
+        ("~1.0", Object::Error(String::from("unknown operator: ~FLOAT"))),
+        ("1.0 + true", Object::Error(String::from("type mismatch: FLOAT + BOOLEAN"))),
+        ("{1.5: 1}", Object::Error(String::from("unusable as hash key: FLOAT"))),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != expected {
+            panic!("{}: expected {:?}. got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_float_display() {
+    let tests = [("2.0", "2.0"), ("1 / 4.0", "0.25"), ("[1, 2.5]", "[1, 2.5]")];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input).to_string();
+        if evaluated != expected {
+            panic!("{}: expected {:?}. got={:?}", input, expected, evaluated);
+        }
+    }
+}
+
+#[test]
+fn test_logical_operators_short_circuit() {
+    let tests = [
+        ("false && missing", Object::Boolean(false)),
+        ("true || 1 / 0", Object::Boolean(true)),
+        ("let f = fn() { missing }; false && f()", Object::Boolean(false)),
+        ("true && missing", Object::Error(String::from("identifier not found: missing"))),
+        ("false || 1 / 0", Object::Error(String::from("division by zero"))),
+    ];
+
+    for (input, expected) in tests {
+        let evaluated = test_eval(input);
+        if evaluated != expected {
+            panic!("{}: expected {:?}. got={:?}", input, expected, evaluated);
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::ast::{self, Node};
+use crate::evaluator::environment::Environment;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Object {
+    Integer(i64),
+    /// An integer outside the 64-bit range; smaller values are always
+    /// `Object::Integer`.
+    #[cfg(feature = "bigint")]
+    BigInt(num_bigint::BigInt),
+    Float(f64),
+    Boolean(bool),
+    String(String),
+    Null,
+    ReturnValue(Box<Object>),
+    Error(String),
+    Function(Rc<Function>),
+    Builtin(Builtin),
+    Array(Vec<Object>),
+    Hash(BTreeMap<HashKey, Object>),
+}
+
+/// The hashable subset of objects, used as keys of `Object::Hash`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum HashKey {
+    Integer(i64),
+    #[cfg(feature = "bigint")]
+    BigInt(num_bigint::BigInt),
+    Boolean(bool),
+    String(String),
+}
+
+impl fmt::Display for HashKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HashKey::Integer(value) => write!(f, "{}", value),
+            #[cfg(feature = "bigint")]
+            HashKey::BigInt(value) => write!(f, "{}", value),
+            HashKey::Boolean(value) => write!(f, "{}", value),
+            HashKey::String(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+pub type BuiltinFunction = fn(Vec<Object>) -> Object;
+
+#[derive(Clone, Copy)]
+pub struct Builtin {
+    pub name: &'static str,
+    pub func: BuiltinFunction,
+}
+
+impl PartialEq for Builtin {
+    fn eq(&self, other: &Builtin) -> bool {
+        self.name == other.name
+    }
+}
+
+impl fmt::Debug for Builtin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Builtin({})", self.name)
+    }
+}
+
+/// A function value together with the environment it was defined in. The
+/// body is shared with the AST, so creating a closure does not copy it.
+/// A function bound in the environment it captures forms a reference cycle,
+/// which `Environment::release` breaks for finished calls where it can.
+pub struct Function {
+    pub parameters: Vec<ast::Identifier>,
+    pub body: Rc<ast::BlockStatement>,
+    pub env: Rc<RefCell<Environment>>,
+}
+
+impl PartialEq for Function {
+    fn eq(&self, other: &Function) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+// The captured environment usually contains the function itself, so it is
+// left out to keep Debug from recursing forever.
+impl fmt::Debug for Function {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Function({})", self)
+    }
+}
+
+impl fmt::Display for Function {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let parameters: Vec<&str> = self.parameters.iter().map(|parameter| parameter.value.as_str()).collect();
+        write!(f, "fn({}) {}", parameters.join(", "), self.body.to_string())
+    }
+}
+
+impl Object {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Object::Integer(_) => "INTEGER",
+            #[cfg(feature = "bigint")]
+            Object::BigInt(_) => "INTEGER",
+            Object::Float(_) => "FLOAT",
+            Object::Boolean(_) => "BOOLEAN",
+            Object::String(_) => "STRING",
+            Object::Null => "NULL",
+            Object::ReturnValue(_) => "RETURN_VALUE",
+            Object::Error(_) => "ERROR",
+            Object::Function(_) => "FUNCTION",
+            Object::Builtin(_) => "BUILTIN",
+            Object::Array(_) => "ARRAY",
+            Object::Hash(_) => "HASH",
+        }
+    }
+
+    pub fn hash_key(&self) -> Option<HashKey> {
+        match self {
+            Object::Integer(value) => Some(HashKey::Integer(*value)),
+            #[cfg(feature = "bigint")]
+            Object::BigInt(value) => Some(HashKey::BigInt(value.clone())),
+            Object::Boolean(value) => Some(HashKey::Boolean(*value)),
+            Object::String(value) => Some(HashKey::String(value.clone())),
+            _ => None,
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self, Object::Error(_))
+    }
+
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Object::Null => false,
+            Object::Boolean(value) => *value,
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Display for Object {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Object::Integer(value) => write!(f, "{}", value),
+            #[cfg(feature = "bigint")]
+            Object::BigInt(value) => write!(f, "{}", value),
+            // Debug formatting keeps the `.0` on whole floats: `2.0`, not `2`.
+            Object::Float(value) => write!(f, "{:?}", value),
+            Object::Boolean(value) => write!(f, "{}", value),
+            Object::String(value) => write!(f, "{}", value),
+            Object::Null => write!(f, "null"),
+            Object::ReturnValue(value) => write!(f, "{}", value),
+            Object::Error(message) => write!(f, "ERROR: {}", message),
+            Object::Function(function) => write!(f, "{}", function),
+            Object::Builtin(builtin) => write!(f, "builtin function {}", builtin.name),
+            Object::Array(elements) => {
+                let elements: Vec<String> = elements.iter().map(|element| element.to_string()).collect();
+                write!(f, "[{}]", elements.join(", "))
+            }
+            Object::Hash(pairs) => {
+                let pairs: Vec<String> = pairs.iter().map(|(key, value)| format!("{}: {}", key, value)).collect();
+                write!(f, "{{{}}}", pairs.join(", "))
+            }
+        }
+    }
+}
@@ -1,10 +1,17 @@
 #[cfg(test)]
 mod mod_test;
 
-use crate::token::{Token, TokenType};
+use crate::token::{BorrowedToken, Span, Token, TokenType};
 
-fn is_letter(character: char) -> bool {
-    matches!(character, 'a'..='z' | 'A'..='Z' | '_')
+/// Identifiers follow Unicode's UAX #31: they start with an XID_Start
+/// character or `_` and continue with XID_Continue characters, which
+/// include digits.
+fn is_identifier_start(character: char) -> bool {
+    character == '_' || unicode_ident::is_xid_start(character)
+}
+
+fn is_identifier_continue(character: char) -> bool {
+    unicode_ident::is_xid_continue(character)
 }
 
 fn look_up_identifier(identifier: &str) -> TokenType {
@@ -25,98 +32,307 @@ fn is_digit(ch: char) -> bool {
 }
 
 
-pub struct Lexer {
-    input: String,
+pub struct Lexer<'src> {
+    input: &'src str,
     position: usize,
-    // current position in input (points to current char)
+    // current byte position in input (points to current char)
     read_position: usize,
-    // current reading position in input (after current char)
+    // current byte reading position in input (after current char)
     ch: char, // current char under examination
+    line: usize, // line of the current char, starting at 1
+    column: usize, // column of the current char, starting at 1
+    finished: bool, // EOF has been yielded by the iterator
+    emit_comments: bool, // return comments as COMMENT tokens instead of skipping them
+}
+
+/// Lexes the whole input, including the trailing EOF token.
+pub fn tokenize(input: &str) -> Vec<Token> {
+    Lexer::new(input).collect()
 }
 
-impl Lexer {
-    pub fn new(input: String) -> Lexer {
-        let mut lexer = Lexer { input, position: 0, read_position: 0, ch: '\0' };
+impl<'src> Lexer<'src> {
+    pub fn new(input: &'src str) -> Lexer<'src> {
+        let mut lexer = Lexer { input, position: 0, read_position: 0, ch: '\0', line: 1, column: 1, finished: false, emit_comments: false };
         lexer.read_char();
         lexer
     }
 
+    /// Creates a lexer that keeps comments as COMMENT tokens, for tools such
+    /// as formatters that need to preserve them.
+    pub fn with_comments(input: &'src str) -> Lexer<'src> {
+        let mut lexer = Lexer::new(input);
+        lexer.emit_comments = true;
+        lexer
+    }
+
     fn read_char(&mut self) {
-        if self.read_position >= self.input.len() {
-            self.ch = '\0';
-        } else {
-            self.ch = self.input.chars().nth(self.read_position).unwrap();
+        if self.read_position > self.position {
+            if self.ch == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
         }
+
         self.position = self.read_position;
-        self.read_position += 1;
+        match self.input[self.read_position..].chars().next() {
+            Some(ch) => {
+                self.ch = ch;
+                self.read_position += ch.len_utf8();
+            }
+            None => self.ch = '\0',
+        }
     }
 
+    /// Returns the next token as an owned `Token`.
     pub fn next_token(&mut self) -> Token {
-        self.skip_whitespace();
+        self.next_borrowed_token().to_owned_token()
+    }
+
+    /// Returns the next token with its literal borrowed from the input.
+    pub fn next_borrowed_token(&mut self) -> BorrowedToken<'src> {
+        loop {
+            self.skip_whitespace();
 
-        let token = match self.ch {
+            let (start, line, column) = (self.position, self.line, self.column);
+            let token_type = self.read_token();
+            if token_type == TokenType::COMMENT && !self.emit_comments {
+                continue;
+            }
+
+            let literal = match token_type {
+                TokenType::EOF => "\0",
+                _ => &self.input[start..self.position],
+            };
+            return BorrowedToken { token_type, literal, span: Span::new(start, self.position, line, column) };
+        }
+    }
+
+    fn read_token(&mut self) -> TokenType {
+        if self.at_eof() {
+            return TokenType::EOF;
+        }
+
+        let token_type = match self.ch {
             '=' => {
-                if self.peak_char() != '=' {
-                    Token { token_type: TokenType::ASSIGN, literal: self.ch.to_string() }
-                } else {
-                    let ch = self.ch;
+                if self.peak_char() == '=' {
                     self.read_char();
-                    let literal = ch.to_string() + &*self.ch.to_string();
-                    Token { token_type: TokenType::EQ, literal }
+                    TokenType::EQ
+                } else {
+                    TokenType::ASSIGN
                 }
             },
-            '+' => Token::new(TokenType::PLUS, self.ch),
-            '-' => Token::new(TokenType::MINUS, self.ch),
+            '+' => TokenType::PLUS,
+            '-' => TokenType::MINUS,
             '!' => {
                 if self.peak_char() == '=' {
-                    let ch = self.ch;
                     self.read_char();
-                    let literal = ch.to_string() + &*self.ch.to_string();
-                    Token { token_type: TokenType::NOT_EQ, literal }
+                    TokenType::NOT_EQ
                 } else {
-                    Token { token_type: TokenType::BANG, literal: self.ch.to_string() }
+                    TokenType::BANG
                 }
             },
-            '/' => Token::new(TokenType::SLASH, self.ch),
-            '*' => Token::new(TokenType::ASTERISK, self.ch),
-            '<' => Token::new(TokenType::LT, self.ch),
-            '>' => Token::new(TokenType::GT, self.ch),
-            ';' => Token::new(TokenType::SEMICOLON, self.ch),
-            '(' => Token::new(TokenType::LPAREN, self.ch),
-            ')' => Token::new(TokenType::RPAREN, self.ch),
-            ',' => Token::new(TokenType::COMMA, self.ch),
-            '{' => Token::new(TokenType::LBRACE, self.ch),
-            '}' => Token::new(TokenType::RBRACE, self.ch),
-            '\0' => Token::new(TokenType::EOF, self.ch),
+            '/' => match self.peak_char() {
+                '/' => return self.read_line_comment(),
+                '*' => return self.read_block_comment(),
+                _ => TokenType::SLASH,
+            },
+            '*' => {
+                if self.peak_char() == '*' {
+                    self.read_char();
+                    TokenType::POWER
+                } else {
+                    TokenType::ASTERISK
+                }
+            },
+            '%' => TokenType::PERCENT,
+            '^' => TokenType::BIT_XOR,
+            '~' => TokenType::TILDE,
+            '<' => match self.peak_char() {
+                '=' => {
+                    self.read_char();
+                    TokenType::LT_EQ
+                }
+                '<' => {
+                    self.read_char();
+                    TokenType::SHL
+                }
+                _ => TokenType::LT,
+            },
+            '>' => match self.peak_char() {
+                '=' => {
+                    self.read_char();
+                    TokenType::GT_EQ
+                }
+                '>' => {
+                    self.read_char();
+                    TokenType::SHR
+                }
+                _ => TokenType::GT,
+            },
+            '&' => {
+                if self.peak_char() == '&' {
+                    self.read_char();
+                    TokenType::AND
+                } else {
+                    TokenType::BIT_AND
+                }
+            },
+            '|' => {
+                if self.peak_char() == '|' {
+                    self.read_char();
+                    TokenType::OR
+                } else {
+                    TokenType::BIT_OR
+                }
+            },
+            ';' => TokenType::SEMICOLON,
+            ':' => TokenType::COLON,
+            '(' => TokenType::LPAREN,
+            ')' => TokenType::RPAREN,
+            ',' => TokenType::COMMA,
+            '{' => TokenType::LBRACE,
+            '}' => TokenType::RBRACE,
+            '[' => TokenType::LBRACKET,
+            ']' => TokenType::RBRACKET,
+            '.' if is_digit(self.peak_char()) => return self.read_number(),
+            '"' => return self.read_string(),
             _ => {
-                return if is_letter(self.ch) {
-                    let identifier = self.read_identifier();
-                    Token { token_type: look_up_identifier(identifier), literal: identifier.to_string() }
+                if is_identifier_start(self.ch) {
+                    return look_up_identifier(self.read_identifier());
                 } else if is_digit(self.ch) {
-                    Token { token_type: TokenType::INT, literal: self.read_number().to_string() }
-                } else {
-                    Token::new(TokenType::ILLEGAL, self.ch)
-                };
+                    return self.read_number();
+                }
+                TokenType::ILLEGAL
             }
         };
         self.read_char();
-        token
+        token_type
     }
 
-    fn read_identifier(&mut self) -> &str {
+    fn read_identifier(&mut self) -> &'src str {
         let position = self.position;
-        while is_letter(self.ch) {
+        while is_identifier_continue(self.ch) {
             self.read_char();
         }
-        self.input.get(position..self.position).unwrap()
+        &self.input[position..self.position]
     }
 
-    fn read_number(&mut self) -> &str {
-        let position = self.position;
-        while is_digit(self.ch) {
+    /// Reads an integer or a float such as `3.14`, `.5` or `1e-9`. The
+    /// fraction and exponent are only consumed when a digit follows them, so
+    /// `1.` and `2e` lex as an INT followed by another token.
+    ///
+    /// `0x`, `0o` and `0b` integers take every alphanumeric character that
+    /// follows, so malformed digits end up in one token the parser can
+    /// report. Digits are not validated here, and `_` separators are kept.
+    /// For the same reason a `.` and digits after a float or a prefixed
+    /// integer stay in the token, so `1.2.3` is not read as `1.2` and `.3`.
+    fn read_number(&mut self) -> TokenType {
+        if self.ch == '0' && matches!(self.peak_char(), 'x' | 'X' | 'o' | 'O' | 'b' | 'B') {
+            self.read_char();
+            self.read_char();
+            self.read_alphanumerics();
+            self.read_extra_fractions();
+            return TokenType::INT;
+        }
+
+        let mut token_type = TokenType::INT;
+        self.read_digits();
+
+        if self.ch == '.' && is_digit(self.peak_char()) {
+            token_type = TokenType::FLOAT;
+            self.read_char();
+            self.read_digits();
+        }
+
+        if self.ch == 'e' || self.ch == 'E' {
+            let rest = &self.input[self.read_position..];
+            let exponent = rest.strip_prefix(['+', '-']).unwrap_or(rest);
+            if exponent.starts_with(|ch: char| ch.is_ascii_digit()) {
+                token_type = TokenType::FLOAT;
+                self.read_char();
+                if self.ch == '+' || self.ch == '-' {
+                    self.read_char();
+                }
+                self.read_digits();
+            }
+        }
+
+        if token_type == TokenType::FLOAT {
+            self.read_extra_fractions();
+        }
+        token_type
+    }
+
+    fn read_digits(&mut self) {
+        while is_digit(self.ch) || self.ch == '_' {
             self.read_char();
         }
-        self.input.get(position..self.position).unwrap()
+    }
+
+    fn read_alphanumerics(&mut self) {
+        while self.ch.is_ascii_alphanumeric() || self.ch == '_' {
+            self.read_char();
+        }
+    }
+
+    fn read_extra_fractions(&mut self) {
+        while self.ch == '.' && is_digit(self.peak_char()) {
+            self.read_char();
+            self.read_alphanumerics();
+        }
+    }
+
+    /// Reads a double-quoted string, leaving escape sequences undecoded in
+    /// the literal. A string that runs into EOF is returned as ILLEGAL.
+    fn read_string(&mut self) -> TokenType {
+        loop {
+            self.read_char();
+            if self.at_eof() {
+                return TokenType::ILLEGAL;
+            }
+            match self.ch {
+                '"' => break,
+                '\\' => self.read_char(),
+                _ => {}
+            }
+        }
+        self.read_char();
+        TokenType::STRING
+    }
+
+    fn read_line_comment(&mut self) -> TokenType {
+        while self.ch != '\n' && !self.at_eof() {
+            self.read_char();
+        }
+        TokenType::COMMENT
+    }
+
+    /// Reads a `/* ... */` comment, which may nest. A comment that runs into
+    /// EOF is returned as ILLEGAL.
+    fn read_block_comment(&mut self) -> TokenType {
+        let mut depth = 0;
+        loop {
+            if self.at_eof() {
+                return TokenType::ILLEGAL;
+            }
+            match (self.ch, self.peak_char()) {
+                ('/', '*') => {
+                    depth += 1;
+                    self.read_char();
+                }
+                ('*', '/') => {
+                    depth -= 1;
+                    self.read_char();
+                }
+                _ => {}
+            }
+            self.read_char();
+            if depth == 0 {
+                return TokenType::COMMENT;
+            }
+        }
     }
 
     fn skip_whitespace(&mut self) {
@@ -125,12 +341,26 @@ impl Lexer {
         }
     }
 
-    fn peak_char(&mut self) -> char {
-        if self.read_position >= self.input.len() {
-            '\0'
-        } else {
-            self.input.chars().nth(self.read_position).unwrap()
-        }
+    /// EOF is tracked by position rather than by `ch`, so a NUL character in
+    /// the source is lexed like any other character.
+    fn at_eof(&self) -> bool {
+        self.position >= self.input.len()
+    }
+
+    fn peak_char(&self) -> char {
+        self.input[self.read_position..].chars().next().unwrap_or('\0')
     }
 }
 
+impl Iterator for Lexer<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        if self.finished {
+            return None;
+        }
+        let token = self.next_token();
+        self.finished = token.token_type == TokenType::EOF;
+        Some(token)
+    }
+}
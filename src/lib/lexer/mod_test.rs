@@ -14,7 +14,7 @@ let add = fn(x, y) {
 };
 
 let result = add(five, ten);
-!-/*5;
+!-/ *5;
 5 < 10 > 5;
 if (5 < 10) {
     return true;
@@ -23,7 +23,9 @@ if (5 < 10) {
 }
 
 10 == 10;
-10 != 9;");
+10 != 9;
+[1, 2];
+{\"foo\": \"bar\"}");
         let tests = vec![
             (TokenType::LET, "let"),
             (TokenType::IDENT, "five"),
@@ -98,10 +100,21 @@ if (5 < 10) {
             (TokenType::NOT_EQ, "!="),
             (TokenType::INT, "9"),
             (TokenType::SEMICOLON, ";"),
+            (TokenType::LBRACKET, "["),
+            (TokenType::INT, "1"),
+            (TokenType::COMMA, ","),
+            (TokenType::INT, "2"),
+            (TokenType::RBRACKET, "]"),
+            (TokenType::SEMICOLON, ";"),
+            (TokenType::LBRACE, "{"),
+            (TokenType::STRING, "\"foo\""),
+            (TokenType::COLON, ":"),
+            (TokenType::STRING, "\"bar\""),
+            (TokenType::RBRACE, "}"),
             (TokenType::EOF, "\0"),
         ];
 
-        let mut l = Lexer::new(input);
+        let mut l = Lexer::new(&input);
         for (i, tt) in tests.iter().enumerate() {
             let tok = l.next_token();
 
@@ -118,4 +131,380 @@ if (5 < 10) {
             );
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_token_spans() {
+        let input = String::from("let x = 10;\n  x == 5");
+        let tests = vec![
+            (TokenType::LET, 0, 3, 1, 1),
+            (TokenType::IDENT, 4, 5, 1, 5),
+            (TokenType::ASSIGN, 6, 7, 1, 7),
+            (TokenType::INT, 8, 10, 1, 9),
+            (TokenType::SEMICOLON, 10, 11, 1, 11),
+            (TokenType::IDENT, 14, 15, 2, 3),
+            (TokenType::EQ, 16, 18, 2, 5),
+            (TokenType::INT, 19, 20, 2, 8),
+            (TokenType::EOF, 20, 20, 2, 9),
+        ];
+
+        let mut l = Lexer::new(&input);
+        for (i, (token_type, start, end, line, column)) in tests.into_iter().enumerate() {
+            let tok = l.next_token();
+
+            assert_eq!(tok.token_type, token_type, "tests[{}] - tokentype wrong", i);
+            assert_eq!(
+                (tok.span.start, tok.span.end, tok.span.line, tok.span.column),
+                (start, end, line, column),
+                "tests[{}] - span wrong for {:?}",
+                i, tok.literal
+            );
+        }
+    }
+
+    #[test]
+    fn test_non_ascii_input() {
+        let input = String::from("é + ten;\n日本 == 5 🎉 \"🎉\" x");
+        let tests = vec![
+            (TokenType::IDENT, "é", 0, 1),
+            (TokenType::PLUS, "+", 3, 3),
+            (TokenType::IDENT, "ten", 5, 5),
+            (TokenType::SEMICOLON, ";", 8, 8),
+            (TokenType::IDENT, "日本", 10, 1),
+            (TokenType::EQ, "==", 17, 4),
+            (TokenType::INT, "5", 20, 7),
+            (TokenType::ILLEGAL, "🎉", 22, 9),
+            (TokenType::STRING, "\"🎉\"", 27, 11),
+            (TokenType::IDENT, "x", 34, 15),
+            (TokenType::EOF, "\0", 35, 16),
+        ];
+
+        let mut l = Lexer::new(&input);
+        for (i, (token_type, literal, start, column)) in tests.into_iter().enumerate() {
+            let tok = l.next_token();
+
+            assert_eq!(tok.token_type, token_type, "tests[{}] - tokentype wrong", i);
+            assert_eq!(tok.literal, literal, "tests[{}] - literal wrong", i);
+            assert_eq!((tok.span.start, tok.span.column), (start, column), "tests[{}] - span wrong", i);
+        }
+    }
+
+    #[test]
+    fn test_unicode_identifiers() {
+        let input = "user2 größe 変数 _tmp1 x_y Ωmega café2 2x";
+        let tests = vec![
+            (TokenType::IDENT, "user2"),
+            (TokenType::IDENT, "größe"),
+            (TokenType::IDENT, "変数"),
+            (TokenType::IDENT, "_tmp1"),
+            (TokenType::IDENT, "x_y"),
+            (TokenType::IDENT, "Ωmega"),
+            (TokenType::IDENT, "café2"),
+            (TokenType::INT, "2"),
+            (TokenType::IDENT, "x"),
+            (TokenType::EOF, "\0"),
+        ];
+
+        let mut l = Lexer::new(input);
+        for (i, (token_type, literal)) in tests.into_iter().enumerate() {
+            let tok = l.next_token();
+
+            assert_eq!(tok.token_type, token_type, "tests[{}] - tokentype wrong", i);
+            assert_eq!(tok.literal, literal, "tests[{}] - literal wrong", i);
+        }
+    }
+
+    #[test]
+    fn test_nul_character_is_not_eof() {
+        let input = "let a = 1; \0 let b = \"x\0y\"; b";
+        let tests = vec![
+            (TokenType::LET, "let"),
+            (TokenType::IDENT, "a"),
+            (TokenType::ASSIGN, "="),
+            (TokenType::INT, "1"),
+            (TokenType::SEMICOLON, ";"),
+            (TokenType::ILLEGAL, "\0"),
+            (TokenType::LET, "let"),
+            (TokenType::IDENT, "b"),
+            (TokenType::ASSIGN, "="),
+            (TokenType::STRING, "\"x\0y\""),
+            (TokenType::SEMICOLON, ";"),
+            (TokenType::IDENT, "b"),
+            (TokenType::EOF, "\0"),
+        ];
+
+        let mut l = Lexer::new(input);
+        for (i, (token_type, literal)) in tests.into_iter().enumerate() {
+            let tok = l.next_token();
+
+            assert_eq!(tok.token_type, token_type, "tests[{}] - tokentype wrong", i);
+            assert_eq!(tok.literal, literal, "tests[{}] - literal wrong", i);
+        }
+
+        let tokens = tokenize("// comment \0 still comment\n/* \0 */ x");
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].literal, "x");
+    }
+
+    #[test]
+    fn test_large_input_lexes() {
+        let input = "let x = 1 + 2;\n".repeat(20_000);
+        let mut l = Lexer::new(&input);
+
+        let mut count = 0;
+        while l.next_token().token_type != TokenType::EOF {
+            count += 1;
+        }
+        assert_eq!(count, 7 * 20_000);
+    }
+
+    #[test]
+    fn test_borrowed_tokens_point_into_input() {
+        let input = "let answer = 42;";
+        let mut l = Lexer::new(input);
+
+        let tests = vec![
+            (TokenType::LET, "let"),
+            (TokenType::IDENT, "answer"),
+            (TokenType::ASSIGN, "="),
+            (TokenType::INT, "42"),
+            (TokenType::SEMICOLON, ";"),
+        ];
+        for (i, (token_type, literal)) in tests.into_iter().enumerate() {
+            let tok = l.next_borrowed_token();
+
+            assert_eq!(tok.token_type, token_type, "tests[{}] - tokentype wrong", i);
+            assert_eq!(tok.literal, literal, "tests[{}] - literal wrong", i);
+            assert_eq!(tok.literal.as_ptr(), input[tok.span.start..].as_ptr(), "tests[{}] - literal is not borrowed", i);
+            assert_eq!(tok.to_owned_token().literal, literal, "tests[{}] - owned literal wrong", i);
+        }
+        assert_eq!(l.next_borrowed_token().token_type, TokenType::EOF);
+    }
+
+    #[test]
+    fn test_lexer_iterator_ends_after_eof() {
+        let tokens: Vec<TokenType> = Lexer::new("let x = 5;").map(|tok| tok.token_type).collect();
+
+        assert_eq!(
+            tokens,
+            vec![TokenType::LET, TokenType::IDENT, TokenType::ASSIGN, TokenType::INT, TokenType::SEMICOLON, TokenType::EOF]
+        );
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize("add(1, two)");
+        let identifiers: Vec<&str> = tokens
+            .iter()
+            .filter(|tok| tok.token_type == TokenType::IDENT)
+            .map(|tok| tok.literal.as_str())
+            .collect();
+
+        assert_eq!(tokens.len(), 7);
+        assert_eq!(tokens.last().map(|tok| tok.token_type), Some(TokenType::EOF));
+        assert_eq!(identifiers, vec!["add", "two"]);
+        assert!(tokenize("").iter().all(|tok| tok.token_type == TokenType::EOF));
+    }
+
+    #[test]
+    fn test_string_tokens() {
+        let input = r#""foobar" "foo bar" "say \"hi\"\n" "" "emoji 🎉 ok" "open"#;
+        let tests = vec![
+            (TokenType::STRING, r#""foobar""#),
+            (TokenType::STRING, r#""foo bar""#),
+            (TokenType::STRING, r#""say \"hi\"\n""#),
+            (TokenType::STRING, r#""""#),
+            (TokenType::STRING, r#""emoji 🎉 ok""#),
+            (TokenType::ILLEGAL, r#""open"#),
+            (TokenType::EOF, "\0"),
+        ];
+
+        let mut l = Lexer::new(input);
+        for (i, (token_type, literal)) in tests.into_iter().enumerate() {
+            let tok = l.next_token();
+
+            assert_eq!(tok.token_type, token_type, "tests[{}] - tokentype wrong", i);
+            assert_eq!(tok.literal, literal, "tests[{}] - literal wrong", i);
+        }
+    }
+
+    #[test]
+    fn test_number_tokens() {
+        let input = "5 3.14 .5 1e-9 2E+3 6.02e23 1. 2e x.5";
+        let tests = vec![
+            (TokenType::INT, "5"),
+            (TokenType::FLOAT, "3.14"),
+            (TokenType::FLOAT, ".5"),
+            (TokenType::FLOAT, "1e-9"),
+            (TokenType::FLOAT, "2E+3"),
+            (TokenType::FLOAT, "6.02e23"),
+            (TokenType::INT, "1"),
+            (TokenType::ILLEGAL, "."),
+            (TokenType::INT, "2"),
+            (TokenType::IDENT, "e"),
+            (TokenType::IDENT, "x"),
+            (TokenType::FLOAT, ".5"),
+            (TokenType::EOF, "\0"),
+        ];
+
+        let mut l = Lexer::new(input);
+        for (i, (token_type, literal)) in tests.into_iter().enumerate() {
+            let tok = l.next_token();
+
+            assert_eq!(tok.token_type, token_type, "tests[{}] - tokentype wrong", i);
+            assert_eq!(tok.literal, literal, "tests[{}] - literal wrong", i);
+        }
+    }
+
+    #[test]
+    fn test_radix_and_separated_integer_tokens() {
+        let input = "0xFF 0b1010 0o755 1_000_000 0XdeadBEEF 0b102 0x 1_000.5 1.2.3 .5.5 1e5.3 0x1.5 1..5";
+        let tests = vec![
+            (TokenType::INT, "0xFF"),
+            (TokenType::INT, "0b1010"),
+            (TokenType::INT, "0o755"),
+            (TokenType::INT, "1_000_000"),
+            (TokenType::INT, "0XdeadBEEF"),
+            (TokenType::INT, "0b102"),
+            (TokenType::INT, "0x"),
+            (TokenType::FLOAT, "1_000.5"),
+            (TokenType::FLOAT, "1.2.3"),
+            (TokenType::FLOAT, ".5.5"),
+            (TokenType::FLOAT, "1e5.3"),
+            (TokenType::INT, "0x1.5"),
+            (TokenType::INT, "1"),
+            (TokenType::ILLEGAL, "."),
+            (TokenType::FLOAT, ".5"),
+            (TokenType::EOF, "\0"),
+        ];
+
+        let mut l = Lexer::new(input);
+        for (i, (token_type, literal)) in tests.into_iter().enumerate() {
+            let tok = l.next_token();
+
+            assert_eq!(tok.token_type, token_type, "tests[{}] - tokentype wrong", i);
+            assert_eq!(tok.literal, literal, "tests[{}] - literal wrong", i);
+        }
+    }
+
+    #[test]
+    fn test_two_character_operators() {
+        let input = "a <= b >= c < d > e && f || g & h | i";
+        let tests = vec![
+            (TokenType::IDENT, "a"),
+            (TokenType::LT_EQ, "<="),
+            (TokenType::IDENT, "b"),
+            (TokenType::GT_EQ, ">="),
+            (TokenType::IDENT, "c"),
+            (TokenType::LT, "<"),
+            (TokenType::IDENT, "d"),
+            (TokenType::GT, ">"),
+            (TokenType::IDENT, "e"),
+            (TokenType::AND, "&&"),
+            (TokenType::IDENT, "f"),
+            (TokenType::OR, "||"),
+            (TokenType::IDENT, "g"),
+            (TokenType::BIT_AND, "&"),
+            (TokenType::IDENT, "h"),
+            (TokenType::BIT_OR, "|"),
+            (TokenType::IDENT, "i"),
+            (TokenType::EOF, "\0"),
+        ];
+
+        let mut l = Lexer::new(input);
+        for (i, (token_type, literal)) in tests.into_iter().enumerate() {
+            let tok = l.next_token();
+
+            assert_eq!(tok.token_type, token_type, "tests[{}] - tokentype wrong", i);
+            assert_eq!(tok.literal, literal, "tests[{}] - literal wrong", i);
+        }
+    }
+
+    #[test]
+    fn test_arithmetic_and_bitwise_operators() {
+        let input = "a % b ** c * d ^ ~e << f >> g";
+        let tests = vec![
+            (TokenType::IDENT, "a"),
+            (TokenType::PERCENT, "%"),
+            (TokenType::IDENT, "b"),
+            (TokenType::POWER, "**"),
+            (TokenType::IDENT, "c"),
+            (TokenType::ASTERISK, "*"),
+            (TokenType::IDENT, "d"),
+            (TokenType::BIT_XOR, "^"),
+            (TokenType::TILDE, "~"),
+            (TokenType::IDENT, "e"),
+            (TokenType::SHL, "<<"),
+            (TokenType::IDENT, "f"),
+            (TokenType::SHR, ">>"),
+            (TokenType::IDENT, "g"),
+            (TokenType::EOF, "\0"),
+        ];
+
+        let mut l = Lexer::new(input);
+        for (i, (token_type, literal)) in tests.into_iter().enumerate() {
+            let tok = l.next_token();
+
+            assert_eq!(tok.token_type, token_type, "tests[{}] - tokentype wrong", i);
+            assert_eq!(tok.literal, literal, "tests[{}] - literal wrong", i);
+        }
+    }
+
+    #[test]
+    fn test_comments_are_skipped() {
+        let input = "// leading comment
+let x = 10 / 2; // trailing
+/* block /* nested */ still comment */ x
+/**/ y";
+        let tests = vec![
+            (TokenType::LET, "let"),
+            (TokenType::IDENT, "x"),
+            (TokenType::ASSIGN, "="),
+            (TokenType::INT, "10"),
+            (TokenType::SLASH, "/"),
+            (TokenType::INT, "2"),
+            (TokenType::SEMICOLON, ";"),
+            (TokenType::IDENT, "x"),
+            (TokenType::IDENT, "y"),
+            (TokenType::EOF, "\0"),
+        ];
+
+        let mut l = Lexer::new(input);
+        for (i, (token_type, literal)) in tests.into_iter().enumerate() {
+            let tok = l.next_token();
+
+            assert_eq!(tok.token_type, token_type, "tests[{}] - tokentype wrong", i);
+            assert_eq!(tok.literal, literal, "tests[{}] - literal wrong", i);
+        }
+    }
+
+    #[test]
+    fn test_unterminated_block_comment() {
+        let input = "x /* open /* nested */\n y";
+        let tokens = tokenize(input);
+
+        assert_eq!(tokens.len(), 3);
+        assert_eq!(tokens[1].token_type, TokenType::ILLEGAL);
+        assert_eq!(tokens[1].literal, "/* open /* nested */\n y");
+        assert_eq!(tokens[1].span, Span::new(2, input.len(), 1, 3));
+        assert_eq!(tokens[2].token_type, TokenType::EOF);
+    }
+
+    #[test]
+    fn test_comments_as_trivia() {
+        let input = "a // note\n/* b */ c";
+        let tokens: Vec<_> = Lexer::with_comments(input)
+            .map(|tok| (tok.token_type, tok.literal))
+            .collect();
+
+        assert_eq!(
+            tokens,
+            vec![
+                (TokenType::IDENT, String::from("a")),
+                (TokenType::COMMENT, String::from("// note")),
+                (TokenType::COMMENT, String::from("/* b */")),
+                (TokenType::IDENT, String::from("c")),
+                (TokenType::EOF, String::from("\0")),
+            ]
+        );
+    }
+}
@@ -2,4 +2,6 @@ pub mod token;
 pub mod lexer;
 pub mod repl;
 pub mod ast;
-pub mod parser;
\ No newline at end of file
+pub mod parser;
+pub mod evaluator;
+pub mod diagnostic;
\ No newline at end of file
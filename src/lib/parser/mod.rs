@@ -1,39 +1,231 @@
+#[cfg(test)]
 mod mod_test;
 
 use std::collections::HashMap;
+use std::rc::Rc;
+use crate::diagnostic::Diagnostic;
 use crate::{ast, lexer, token};
 
-type PrefixParseFn = fn(&mut Parser) -> Option<ast::Expression>;
-type InfixParseFn = fn(&mut Parser, ast::Expression) -> Option<ast::Expression>;
+type PrefixParseFn<'src> = fn(&mut Parser<'src>) -> Option<ast::Expression>;
+type InfixParseFn<'src> = fn(&mut Parser<'src>, ast::Expression) -> Option<ast::Expression>;
 
-#[derive(PartialEq, PartialOrd)]
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 pub enum Precedence {
     Lowest,
+    LogicalOr,   // ||
+    LogicalAnd,  // &&
     Equals,      // ==
-    LessGreater, // > or <
+    LessGreater, // >, <, >= or <=
+    BitOr,       // |
+    BitXor,      // ^
+    BitAnd,      // &
+    Shift,       // << or >>
     Sum,         // +
-    Product,     // *
-    Prefix,      // -X or !X
+    Product,     // *, / or %
+    Prefix,      // -X, !X or ~X
+    Exponent,    // ** (binds tighter than a prefix operator on its left: -2 ** 2 == -4)
     Call,        // myFunction(X)
+    Index,       // array[index]
 }
 
-pub struct Parser {
-    lexer: lexer::Lexer,
+fn token_precedence(token_type: token::TokenType) -> Precedence {
+    match token_type {
+        token::TokenType::OR => Precedence::LogicalOr,
+        token::TokenType::AND => Precedence::LogicalAnd,
+        token::TokenType::EQ | token::TokenType::NOT_EQ => Precedence::Equals,
+        token::TokenType::LT | token::TokenType::GT | token::TokenType::LT_EQ | token::TokenType::GT_EQ => {
+            Precedence::LessGreater
+        }
+        token::TokenType::BIT_OR => Precedence::BitOr,
+        token::TokenType::BIT_XOR => Precedence::BitXor,
+        token::TokenType::BIT_AND => Precedence::BitAnd,
+        token::TokenType::SHL | token::TokenType::SHR => Precedence::Shift,
+        token::TokenType::PLUS | token::TokenType::MINUS => Precedence::Sum,
+        token::TokenType::SLASH | token::TokenType::ASTERISK | token::TokenType::PERCENT => Precedence::Product,
+        token::TokenType::POWER => Precedence::Exponent,
+        token::TokenType::LPAREN => Precedence::Call,
+        token::TokenType::LBRACKET => Precedence::Index,
+        _ => Precedence::Lowest,
+    }
+}
+
+/// Decodes an integer literal: decimal or `0x`/`0o`/`0b` prefixed, with `_`
+/// separators between digits. Returns the error message on failure.
+fn decode_integer(literal: &str) -> Result<i64, String> {
+    let (radix, kind, digits) = split_radix(literal);
+    if digits.is_empty() {
+        return Err(format!("missing digits in {} literal {}", kind, literal));
+    }
+
+    if let Some(ch) = digits.chars().find(|&ch| ch != '_' && !ch.is_digit(radix)) {
+        return Err(format!("invalid digit {:?} in {} literal {}", ch, kind, literal));
+    }
+    let digits = remove_separators(digits, radix).ok_or_else(|| format!("misplaced `_` in integer literal {}", literal))?;
+    i64::from_str_radix(&digits, radix).map_err(|_| format!("integer literal {} is too large for a 64-bit integer", literal))
+}
+
+/// Splits an integer literal into its radix, the radix name and the digits
+/// after any `0x`/`0o`/`0b` prefix.
+fn split_radix(literal: &str) -> (u32, &'static str, &str) {
+    match literal.get(..2) {
+        Some("0x" | "0X") => (16, "hexadecimal", &literal[2..]),
+        Some("0o" | "0O") => (8, "octal", &literal[2..]),
+        Some("0b" | "0B") => (2, "binary", &literal[2..]),
+        _ => (10, "decimal", literal),
+    }
+}
+
+/// Decodes a well-formed integer literal of any size.
+#[cfg(feature = "bigint")]
+fn decode_big_integer(literal: &str) -> Option<num_bigint::BigInt> {
+    let (radix, _, digits) = split_radix(literal);
+    num_bigint::BigInt::parse_bytes(remove_separators(digits, radix)?.as_bytes(), radix)
+}
+
+/// Decodes a float literal such as `1_000.5` or `6.02e23`. Separators are
+/// checked in the integer, fraction and exponent digits separately, so a
+/// `_` next to the `.` or the `e` is rejected.
+fn decode_float(literal: &str) -> Result<f64, String> {
+    let (mantissa, exponent) = match literal.find(['e', 'E']) {
+        Some(index) => (&literal[..index], Some(&literal[index + 1..])),
+        None => (literal, None),
+    };
+    let (integer, fraction) = match mantissa.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (mantissa, None),
+    };
+
+    let misplaced = || format!("misplaced `_` in float literal {}", literal);
+    let mut decoded = remove_separators(integer, 10).ok_or_else(misplaced)?;
+    if let Some(fraction) = fraction {
+        decoded.push('.');
+        decoded.push_str(&remove_separators(fraction, 10).ok_or_else(misplaced)?);
+    }
+    if let Some(exponent) = exponent {
+        let (sign, digits) = match exponent.strip_prefix(['+', '-']) {
+            Some(digits) => (&exponent[..1], digits),
+            None => ("", exponent),
+        };
+        decoded.push('e');
+        decoded.push_str(sign);
+        decoded.push_str(&remove_separators(digits, 10).ok_or_else(misplaced)?);
+    }
+    decoded.parse::<f64>().map_err(|_| format!("could not parse {} as float", literal))
+}
+
+/// Drops `_` digit separators, or returns None if one is not between two
+/// digits of `radix`.
+fn remove_separators(digits: &str, radix: u32) -> Option<String> {
+    let bytes = digits.as_bytes();
+    let is_digit = |byte: u8| char::from(byte).is_digit(radix);
+    for (i, &byte) in bytes.iter().enumerate() {
+        if byte == b'_' {
+            let before = i.checked_sub(1).map(|i| bytes[i]);
+            let after = bytes.get(i + 1).copied();
+            if !before.is_some_and(is_digit) || !after.is_some_and(is_digit) {
+                return None;
+            }
+        }
+    }
+    Some(digits.replace('_', ""))
+}
+
+/// Decodes the escape sequences in the body of a string literal. On failure
+/// returns the byte range of the offending escape within `raw`.
+fn unescape(raw: &str) -> Result<String, (usize, usize)> {
+    let mut value = String::with_capacity(raw.len());
+    let mut chars = raw.char_indices().peekable();
+
+    while let Some((start, ch)) = chars.next() {
+        if ch != '\\' {
+            value.push(ch);
+            continue;
+        }
+
+        let decoded = match chars.next() {
+            Some((_, 'n')) => Some('\n'),
+            Some((_, 't')) => Some('\t'),
+            Some((_, '"')) => Some('"'),
+            Some((_, '\\')) => Some('\\'),
+            Some((_, 'u')) if chars.next_if(|&(_, c)| c == '{').is_some() => {
+                let mut digits = String::new();
+                while let Some((_, digit)) = chars.next_if(|&(_, c)| c.is_ascii_hexdigit()) {
+                    digits.push(digit);
+                }
+                chars
+                    .next_if(|&(_, c)| c == '}')
+                    .filter(|_| !digits.is_empty() && digits.len() <= 6)
+                    .and_then(|_| u32::from_str_radix(&digits, 16).ok())
+                    .and_then(char::from_u32)
+            }
+            _ => None,
+        };
+
+        match decoded {
+            Some(decoded) => value.push(decoded),
+            None => return Err((start, chars.peek().map_or(raw.len(), |&(end, _)| end))),
+        }
+    }
+
+    Ok(value)
+}
+
+pub struct Parser<'src> {
+    lexer: lexer::Lexer<'src>,
     current_token: token::Token,
     peek_token: token::Token,
-    errors: Vec<String>,
-    prefix_parse_fns: HashMap<token::TokenType, PrefixParseFn>,
-    infix_parse_fns: HashMap<token::TokenType, InfixParseFn>,
+    errors: Vec<Diagnostic>,
+    brace_depth: isize, // unclosed `{` before current_token
+    prefix_parse_fns: HashMap<token::TokenType, PrefixParseFn<'src>>,
+    infix_parse_fns: HashMap<token::TokenType, InfixParseFn<'src>>,
 }
 
-impl Parser {
-    pub fn new(mut lexer: lexer::Lexer) -> Parser {
+impl<'src> Parser<'src> {
+    pub fn new(mut lexer: lexer::Lexer<'src>) -> Parser<'src> {
         let current_token = lexer.next_token();
         let peek_token = lexer.next_token();
-        let mut p = Parser { lexer, current_token, peek_token, errors: Vec::new(), prefix_parse_fns: HashMap::new(), infix_parse_fns: HashMap::new() };
+        let mut p = Parser { lexer, current_token, peek_token, errors: Vec::new(), brace_depth: 0, prefix_parse_fns: HashMap::new(), infix_parse_fns: HashMap::new() };
         p.register_prefix(token::TokenType::IDENT, Parser::parse_identifier );
         p.register_prefix(token::TokenType::INT, Parser::parse_integer_literal );
-        return p;
+        p.register_prefix(token::TokenType::FLOAT, Parser::parse_float_literal);
+        p.register_prefix(token::TokenType::STRING, Parser::parse_string_literal);
+        p.register_prefix(token::TokenType::BANG, Parser::parse_prefix_expression);
+        p.register_prefix(token::TokenType::MINUS, Parser::parse_prefix_expression);
+        p.register_prefix(token::TokenType::TILDE, Parser::parse_prefix_expression);
+        p.register_prefix(token::TokenType::TRUE, Parser::parse_boolean);
+        p.register_prefix(token::TokenType::FALSE, Parser::parse_boolean);
+        p.register_prefix(token::TokenType::LPAREN, Parser::parse_grouped_expression);
+        p.register_prefix(token::TokenType::IF, Parser::parse_if_expression);
+        p.register_prefix(token::TokenType::FUNCTION, Parser::parse_function_literal);
+        p.register_prefix(token::TokenType::LBRACKET, Parser::parse_array_literal);
+        p.register_prefix(token::TokenType::LBRACE, Parser::parse_hash_literal);
+
+        for token_type in [
+            token::TokenType::PLUS,
+            token::TokenType::MINUS,
+            token::TokenType::ASTERISK,
+            token::TokenType::SLASH,
+            token::TokenType::EQ,
+            token::TokenType::NOT_EQ,
+            token::TokenType::LT,
+            token::TokenType::GT,
+            token::TokenType::LT_EQ,
+            token::TokenType::GT_EQ,
+            token::TokenType::AND,
+            token::TokenType::OR,
+            token::TokenType::PERCENT,
+            token::TokenType::POWER,
+            token::TokenType::BIT_AND,
+            token::TokenType::BIT_OR,
+            token::TokenType::BIT_XOR,
+            token::TokenType::SHL,
+            token::TokenType::SHR,
+        ] {
+            p.register_infix(token_type, Parser::parse_infix_expression);
+        }
+        p.register_infix(token::TokenType::LPAREN, Parser::parse_call_expression);
+        p.register_infix(token::TokenType::LBRACKET, Parser::parse_index_expression);
+        p
     }
 
     pub fn parse_identifier(&mut self) -> Option<ast::Expression> {
@@ -46,23 +238,316 @@ impl Parser {
     }
 
     pub fn parse_integer_literal(&mut self) -> Option<ast::Expression> {
-        let current_token = self.current_token.clone();
-        let value = self.current_token.literal.parse::<i64>();
-        match value {
-            Ok(val) => {
-                let integer_literal = ast::IntegerLiteral { token: current_token.clone(), value: val };
+        match decode_integer(&self.current_token.literal) {
+            Ok(value) => {
+                let integer_literal = ast::IntegerLiteral { token: self.current_token.clone(), value };
                 Some(ast::Expression::IntegerLiteral(integer_literal))
-            },
-            Err(..) => {
-                let error = format!("could not parse {} as integer", self.current_token.literal);
+            }
+            Err(message) => {
+                #[cfg(feature = "bigint")]
+                if let Some(value) = decode_big_integer(&self.current_token.literal) {
+                    let literal = ast::BigIntegerLiteral { token: self.current_token.clone(), value };
+                    return Some(ast::Expression::BigIntegerLiteral(literal));
+                }
+
+                self.errors.push(Diagnostic::error("E0003", message, self.current_token.span));
+                None
+            }
+        }
+    }
+
+    pub fn parse_float_literal(&mut self) -> Option<ast::Expression> {
+        match decode_float(&self.current_token.literal) {
+            Ok(value) => {
+                let float_literal = ast::FloatLiteral { token: self.current_token.clone(), value };
+                Some(ast::Expression::FloatLiteral(float_literal))
+            }
+            Err(message) => {
+                self.errors.push(Diagnostic::error("E0003", message, self.current_token.span));
+                None
+            }
+        }
+    }
+
+    pub fn parse_string_literal(&mut self) -> Option<ast::Expression> {
+        let token = self.current_token.clone();
+        let raw = &token.literal[1..token.literal.len() - 1];
+
+        match unescape(raw) {
+            Ok(value) => Some(ast::Expression::StringLiteral(ast::StringLiteral { token, value })),
+            Err((start, end)) => {
+                let column = token.span.column + 1 + raw[..start].chars().count();
+                let offset = token.span.start + 1;
+                let span = token::Span::new(offset + start, offset + end, token.span.line, column);
+                let error = Diagnostic::error("E0006", format!("invalid escape sequence {}", &raw[start..end]), span)
+                    .with_help("supported escapes are \\n, \\t, \\\", \\\\ and \\u{...}");
                 self.errors.push(error);
                 None
             }
         }
+    }
 
+    pub fn parse_boolean(&mut self) -> Option<ast::Expression> {
+        let boolean = ast::Boolean {
+            token: self.current_token.clone(),
+            value: self.current_token_is(token::TokenType::TRUE),
+        };
+        Some(ast::Expression::Boolean(boolean))
+    }
+
+    pub fn parse_grouped_expression(&mut self) -> Option<ast::Expression> {
+        let opening = self.current_token.span;
+        self.next_token();
+
+        let expression = self.parse_expression(Precedence::Lowest)?;
+
+        if !self.expect_peek(token::TokenType::RPAREN) {
+            if let Some(error) = self.errors.pop() {
+                self.errors.push(error.with_label(opening, "unclosed delimiter"));
+            }
+            return None;
+        }
+        Some(expression)
+    }
+
+    pub fn parse_if_expression(&mut self) -> Option<ast::Expression> {
+        let current_token = self.current_token.clone();
+
+        if !self.expect_peek(token::TokenType::LPAREN) {
+            return None;
+        }
+        self.next_token();
+        let condition = self.parse_expression(Precedence::Lowest)?;
+
+        if !self.expect_peek(token::TokenType::RPAREN) {
+            return None;
+        }
+        if !self.expect_peek(token::TokenType::LBRACE) {
+            return None;
+        }
+        let consequence = self.parse_block_statement()?;
+
+        let mut alternative = None;
+        if self.peek_token_is(token::TokenType::ELSE) {
+            self.next_token();
+            if !self.expect_peek(token::TokenType::LBRACE) {
+                return None;
+            }
+            alternative = Some(Box::new(self.parse_block_statement()?));
+        }
+
+        let expression = ast::IfExpression { token: current_token, condition: Box::new(condition), consequence: Box::new(consequence), alternative };
+        Some(ast::Expression::IfExpression(expression))
+    }
+
+    pub fn parse_block_statement(&mut self) -> Option<ast::BlockStatement> {
+        let current_token = self.current_token.clone();
+        let mut statements = Vec::new();
+
+        self.next_token();
+        while !self.current_token_is(token::TokenType::RBRACE) {
+            if self.current_token_is(token::TokenType::EOF) {
+                let error = Diagnostic::error("E0001", String::from("expected RBRACE, got EOF instead"), self.current_token.span)
+                    .with_label(current_token.span, "unclosed delimiter");
+                self.errors.push(error);
+                return None;
+            }
+            let depth = self.brace_depth;
+            match self.parse_statement() {
+                Some(statement) => statements.push(statement),
+                None => {
+                    self.synchronize(depth);
+                    if self.current_token_is(token::TokenType::RBRACE) {
+                        continue;
+                    }
+                }
+            }
+            self.next_token();
+        }
+
+        Some(ast::BlockStatement { token: current_token, statements, closing: self.current_token.span })
+    }
+
+    pub fn parse_function_literal(&mut self) -> Option<ast::Expression> {
+        let current_token = self.current_token.clone();
+
+        if !self.expect_peek(token::TokenType::LPAREN) {
+            return None;
+        }
+        let parameters = self.parse_function_parameters()?;
+
+        if !self.expect_peek(token::TokenType::LBRACE) {
+            return None;
+        }
+        let body = self.parse_block_statement()?;
+
+        let function = ast::FunctionLiteral { token: current_token, parameters, body: Rc::new(body) };
+        Some(ast::Expression::FunctionLiteral(function))
+    }
+
+    fn parse_function_parameters(&mut self) -> Option<Vec<ast::Identifier>> {
+        let mut identifiers = Vec::new();
+
+        if self.peek_token_is(token::TokenType::RPAREN) {
+            self.next_token();
+            return Some(identifiers);
+        }
+
+        loop {
+            if !self.expect_peek(token::TokenType::IDENT) {
+                return None;
+            }
+            identifiers.push(ast::Identifier { token: self.current_token.clone(), value: self.current_token.literal.clone() });
+
+            if !self.peek_token_is(token::TokenType::COMMA) {
+                break;
+            }
+            self.next_token();
+        }
+
+        if !self.expect_peek(token::TokenType::RPAREN) {
+            return None;
+        }
+        Some(identifiers)
+    }
+
+    pub fn parse_call_expression(&mut self, function: ast::Expression) -> Option<ast::Expression> {
+        let current_token = self.current_token.clone();
+        let arguments = self.parse_expression_list(token::TokenType::RPAREN)?;
+
+        let expression = ast::CallExpression {
+            token: current_token,
+            function: Box::new(function),
+            arguments,
+            closing: self.current_token.span,
+        };
+        Some(ast::Expression::CallExpression(expression))
+    }
+
+    pub fn parse_array_literal(&mut self) -> Option<ast::Expression> {
+        let current_token = self.current_token.clone();
+        let elements = self.parse_expression_list(token::TokenType::RBRACKET)?;
+
+        let array = ast::ArrayLiteral { token: current_token, elements, closing: self.current_token.span };
+        Some(ast::Expression::ArrayLiteral(array))
+    }
+
+    /// Block statements are only parsed where the grammar expects one (after
+    /// `if (...)`, `else` and `fn(...)`), so a `{` reaching parse_expression
+    /// always starts a hash literal.
+    pub fn parse_hash_literal(&mut self) -> Option<ast::Expression> {
+        let current_token = self.current_token.clone();
+        let mut pairs = Vec::new();
+
+        if !self.peek_token_is(token::TokenType::RBRACE) {
+            loop {
+                self.next_token();
+                let key = self.parse_expression(Precedence::Lowest)?;
+
+                if !self.expect_peek(token::TokenType::COLON) {
+                    return None;
+                }
+
+                self.next_token();
+                let value = self.parse_expression(Precedence::Lowest)?;
+                pairs.push((key, value));
+
+                // Like the other comma-separated lists, a trailing comma is an
+                // error: the next pair must follow it.
+                if self.peek_token_is(token::TokenType::RBRACE) {
+                    break;
+                }
+                if !self.expect_peek(token::TokenType::COMMA) {
+                    return None;
+                }
+            }
+        }
+
+        if !self.expect_peek(token::TokenType::RBRACE) {
+            return None;
+        }
+
+        let hash = ast::HashLiteral { token: current_token, pairs, closing: self.current_token.span };
+        Some(ast::Expression::HashLiteral(hash))
+    }
+
+    pub fn parse_index_expression(&mut self, left: ast::Expression) -> Option<ast::Expression> {
+        let current_token = self.current_token.clone();
+
+        self.next_token();
+        let index = self.parse_expression(Precedence::Lowest)?;
+
+        if !self.expect_peek(token::TokenType::RBRACKET) {
+            return None;
+        }
+
+        let expression = ast::IndexExpression {
+            token: current_token,
+            left: Box::new(left),
+            index: Box::new(index),
+            closing: self.current_token.span,
+        };
+        Some(ast::Expression::IndexExpression(expression))
+    }
+
+    /// Parses comma separated expressions up to and including `end`.
+    fn parse_expression_list(&mut self, end: token::TokenType) -> Option<Vec<ast::Expression>> {
+        let mut list = Vec::new();
+
+        if self.peek_token_is(end) {
+            self.next_token();
+            return Some(list);
+        }
+
+        self.next_token();
+        list.push(self.parse_expression(Precedence::Lowest)?);
+
+        while self.peek_token_is(token::TokenType::COMMA) {
+            self.next_token();
+            self.next_token();
+            list.push(self.parse_expression(Precedence::Lowest)?);
+        }
+
+        if !self.expect_peek(end) {
+            return None;
+        }
+        Some(list)
+    }
+
+    pub fn parse_prefix_expression(&mut self) -> Option<ast::Expression> {
+        let current_token = self.current_token.clone();
+        let operator = self.current_token.literal.clone();
+
+        self.next_token();
+
+        let right = self.parse_expression(Precedence::Prefix)?;
+        let expression = ast::PrefixExpression { token: current_token, operator, right: Box::new(right) };
+        Some(ast::Expression::PrefixExpression(expression))
+    }
+
+    pub fn parse_infix_expression(&mut self, left: ast::Expression) -> Option<ast::Expression> {
+        let current_token = self.current_token.clone();
+        let operator = self.current_token.literal.clone();
+        // `**` is right-associative: parsing its right operand one level
+        // lower lets a following `**` bind to that operand first.
+        let precedence = match self.current_token.token_type {
+            token::TokenType::POWER => Precedence::Prefix,
+            _ => self.current_precedence(),
+        };
+
+        self.next_token();
+
+        let right = self.parse_expression(precedence)?;
+        let expression = ast::InfixExpression { token: current_token, left: Box::new(left), operator, right: Box::new(right) };
+        Some(ast::Expression::InfixExpression(expression))
     }
 
     pub fn next_token(&mut self) {
+        match self.current_token.token_type {
+            token::TokenType::LBRACE => self.brace_depth += 1,
+            token::TokenType::RBRACE => self.brace_depth -= 1,
+            _ => {}
+        }
         self.current_token = std::mem::replace(&mut self.peek_token, self.lexer.next_token());
     }
 
@@ -77,31 +562,111 @@ impl Parser {
     pub fn parse_expression_statement(&mut self) -> Option<ast::Statement> {
         let current_token = self.current_token.clone();
 
-        let expression = self.parse_expression(Precedence::Lowest).unwrap();
+        let expression = self.parse_expression(Precedence::Lowest)?;
 
         if self.peek_token_is(token::TokenType::SEMICOLON) {
             self.next_token();
         }
 
-        let statement = ast::ExpressionStatement { token: current_token.clone(), expression };
-        return Some(ast::Statement::ExpressionStatement(statement));
+        let statement = ast::ExpressionStatement { token: current_token, expression: Some(expression) };
+        Some(ast::Statement::ExpressionStatement(statement))
+    }
+
+    pub fn parse_expression(&mut self, precedence: Precedence) -> Option<ast::Expression> {
+        let prefix = match self.prefix_parse_fns.get(&self.current_token.token_type) {
+            Some(prefix) => *prefix,
+            None => {
+                self.no_prefix_parse_fn_error();
+                return None;
+            }
+        };
+        let mut left = prefix(self)?;
+
+        while !self.peek_token_is(token::TokenType::SEMICOLON) && precedence < self.peek_precedence() {
+            let infix = match self.infix_parse_fns.get(&self.peek_token.token_type) {
+                Some(infix) => *infix,
+                None => return Some(left),
+            };
+            self.next_token();
+            left = infix(self, left)?;
+        }
+
+        Some(left)
+    }
 
+    pub fn peek_precedence(&self) -> Precedence {
+        token_precedence(self.peek_token.token_type)
     }
 
-    pub fn parse_expression(&mut self, _precedence: Precedence) -> Option<Option<ast::Expression>> {
-        let prefix = self.prefix_parse_fns.get(&self.current_token.token_type)?; 
-        return Some(prefix(self));
+    pub fn current_precedence(&self) -> Precedence {
+        token_precedence(self.current_token.token_type)
     }
 
     pub fn errors(&self) -> Vec<String> {
-        self.errors.clone()
+        self.errors.iter().map(|error| error.to_string()).collect()
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.errors
     }
 
     pub fn peek_error(&mut self, token_type: token::TokenType) {
-        let error = format!("expected next token to be {:?}, got {:?} instead", token_type, self.peek_token.token_type);
+        let message = format!("expected next token to be {}, got {} instead", token_type, self.peek_token.token_type);
+        let error = Diagnostic::error("E0001", message, self.peek_token.span)
+            .with_label(self.current_token.span, &format!("after this {}", self.current_token.token_type));
         self.errors.push(error);
     }
 
+    fn no_prefix_parse_fn_error(&mut self) {
+        let token = &self.current_token;
+        let error = if token.token_type == token::TokenType::ILLEGAL && token.literal.starts_with('"') {
+            Diagnostic::error("E0005", String::from("unterminated string literal"), token.span)
+                .with_help("add a closing `\"` to end the string")
+        } else if token.token_type == token::TokenType::ILLEGAL && token.literal.starts_with("/*") {
+            Diagnostic::error("E0005", String::from("unterminated block comment"), token.span)
+                .with_help("add a closing `*/`; block comments nest, so every `/*` needs one")
+        } else if token.token_type == token::TokenType::ILLEGAL {
+            Diagnostic::error("E0005", format!("illegal character {:?}", token.literal), token.span)
+        } else if token.token_type == token::TokenType::EOF {
+            Diagnostic::error("E0002", String::from("expected an expression, got EOF instead"), token.span)
+                .with_help("the statement is incomplete; add a value before the end of the input")
+        } else {
+            let message = format!("no prefix parse function for {} found", token.token_type);
+            Diagnostic::error("E0004", message, token.span)
+                .with_help(&format!("`{}` cannot start an expression", token.literal))
+        };
+        self.errors.push(error);
+    }
+
+    /// Skips ahead after a failed statement that started at brace `depth` so
+    /// parsing resumes at the next statement boundary: a semicolon, the brace
+    /// closing the enclosing block, or a `let`/`return`. Braces opened by the
+    /// failed statement itself are skipped as a whole.
+    fn synchronize(&mut self, depth: isize) {
+        loop {
+            let depth_after = self.brace_depth
+                + match self.current_token.token_type {
+                    token::TokenType::LBRACE => 1,
+                    token::TokenType::RBRACE => -1,
+                    _ => 0,
+                };
+            match self.current_token.token_type {
+                token::TokenType::EOF => return,
+                token::TokenType::SEMICOLON | token::TokenType::RBRACE if self.brace_depth == depth => return,
+                _ => {}
+            }
+            if depth_after == depth
+                && matches!(
+                    self.peek_token.token_type,
+                    token::TokenType::LET | token::TokenType::RETURN | token::TokenType::EOF
+                )
+            {
+                return;
+            }
+            self.next_token();
+        }
+    }
+
     pub fn parse_let_statement(&mut self) -> Option<ast::Statement> {
         let current_token = self.current_token.clone();
 
@@ -115,26 +680,29 @@ impl Parser {
             return None;
         }
 
-        while !self.current_token_is(token::TokenType::SEMICOLON) {
-            self.next_token();
-        };
+        self.next_token();
+        let value = self.parse_expression(Precedence::Lowest)?;
 
-        let expression = None;
-        let statement = ast::LetStatement { token: current_token.clone(), name, value: expression };
+        if self.peek_token_is(token::TokenType::SEMICOLON) {
+            self.next_token();
+        }
 
+        let statement = ast::LetStatement { token: current_token, name, value: Some(value) };
         Some(ast::Statement::LetStatement(statement))
     }
 
     pub fn parse_return_statement(&mut self) -> Option<ast::Statement> {
         let current_token = self.current_token.clone();
+
         self.next_token();
+        let return_value = self.parse_expression(Precedence::Lowest)?;
 
-        while !self.current_token_is(token::TokenType::SEMICOLON) {
+        if self.peek_token_is(token::TokenType::SEMICOLON) {
             self.next_token();
-        };
+        }
 
-        let statement = ast::ReturnStatement { token: current_token.clone(), return_value: None };
-        return Some(ast::Statement::ReturnStatement(statement));
+        let statement = ast::ReturnStatement { token: current_token, return_value: Some(return_value) };
+        Some(ast::Statement::ReturnStatement(statement))
     }
 
     pub fn current_token_is(&self, token_type: token::TokenType) -> bool {
@@ -159,20 +727,21 @@ impl Parser {
     pub fn parse_program(&mut self) -> Option<ast::Program> {
         let mut program = ast::Program { statements: vec![] };
         while !self.current_token_is(token::TokenType::EOF) {
-            let statement = self.parse_statement();
-            if let Some(..) = statement {
-                program.statements.push(statement.unwrap());
+            let depth = self.brace_depth;
+            match self.parse_statement() {
+                Some(statement) => program.statements.push(statement),
+                None => self.synchronize(depth),
             }
             self.next_token();
         }
         Some(program)
     }
 
-    pub fn register_prefix(&mut self, token_type: token::TokenType, func: PrefixParseFn) {
+    pub fn register_prefix(&mut self, token_type: token::TokenType, func: PrefixParseFn<'src>) {
         self.prefix_parse_fns.insert(token_type, func);
     }
 
-    pub fn register_infix(&mut self, token_type: token::TokenType, func: InfixParseFn) {
+    pub fn register_infix(&mut self, token_type: token::TokenType, func: InfixParseFn<'src>) {
         self.infix_parse_fns.insert(token_type, func);
     }
 }
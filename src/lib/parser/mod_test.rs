@@ -1,9 +1,9 @@
-#[cfg(test)]
 use crate::lexer::Lexer;
 use crate::ast;
 use crate::ast::Node;
 use crate::parser::Parser;
 
+#[allow(clippy::needless_return)]
 fn test_let_statement(statement: &ast::Statement, name: &str) -> bool {
     match statement {
         ast::Statement::LetStatement(let_stmt) => {
@@ -30,7 +30,7 @@ fn test_let_statements() {
 let y = 10;\
 let foobar = 838383;";
 
-    let lex = Lexer::new(input.to_string());
+    let lex = Lexer::new(input);
     let mut parser = Parser::new(lex);
     let program = parser.parse_program().unwrap_or_else(|| panic!("parse_program() returned None"));
     check_parser_errors(parser);
@@ -39,17 +39,68 @@ let foobar = 838383;";
         panic!("program.statements does not contain 3 statements. got={}", program.statements.len());
     }
 
-    let tests = vec!["x", "y", "foobar"];
+    let tests = [("x", 5), ("y", 10), ("foobar", 838383)];
 
-    for (i, tt) in tests.iter().enumerate() {
+    for (i, (name, value)) in tests.iter().enumerate() {
         let stmt = program.statements.get(i).unwrap();
-        if !test_let_statement(stmt, tt) {
-            return;
+        if !test_let_statement(stmt, name) {
+            panic!("statement {} is not a let statement for {}", i, name);
+        }
+        if let ast::Statement::LetStatement(let_stmt) = stmt {
+            match &let_stmt.value {
+                Some(expression) if test_integer_literal(expression, *value) => {}
+                _ => panic!("let_stmt.value is not {}. got={:?}", value, let_stmt.value),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_let_and_return_without_trailing_semicolon() {
+    let input = "let x = 1 + 2\nreturn x";
+
+    let lex = Lexer::new(input);
+    let mut parser = Parser::new(lex);
+    let program = parser.parse_program().unwrap_or_else(|| panic!("parse_program() returned None"));
+    check_parser_errors(parser);
+
+    if program.statements.len() != 2 {
+        panic!("program.statements does not contain 2 statements. got={}", program.statements.len());
+    }
+    if !test_let_statement(&program.statements[0], "x") {
+        panic!("program.statements[0] is not a let statement for x");
+    }
+    match &program.statements[1] {
+        ast::Statement::ReturnStatement(ast::ReturnStatement { return_value: Some(ast::Expression::Identifier(ident)), .. }) => {
+            if ident.value != "x" {
+                panic!("return value is not x. got={}", ident.value);
+            }
         }
+        statement => panic!("program.statements[1] is not a return statement. got={:?}", statement),
     }
 }
 
-fn check_parser_errors(parser: Parser) {
+#[test]
+fn test_let_and_return_at_eof_report_errors() {
+    let tests = [("let x =", "1:8"), ("return", "1:7")];
+
+    for (input, location) in tests {
+        let lex = Lexer::new(input);
+        let mut parser = Parser::new(lex);
+        parser.parse_program().unwrap_or_else(|| panic!("parse_program() returned None"));
+
+        let errors = parser.errors();
+        if errors.len() != 1 {
+            panic!("{:?}: expected 1 parser error. got={:?}", input, errors);
+        }
+        if errors[0] != format!("{}: expected an expression, got EOF instead", location) {
+            panic!("{:?}: wrong parser error. got={}", input, errors[0]);
+        }
+    }
+}
+
+#[allow(clippy::len_zero)]
+fn check_parser_errors(parser: Parser<'_>) {
     let errors = parser.errors();
     if errors.len() == 0 {
         return;
@@ -67,8 +118,9 @@ fn test_return_statements() {
     let input = "return 5;\
 return 10;\
 return 993322;";
+    let expected = [5, 10, 993322];
 
-    let lex = Lexer::new(input.to_string());
+    let lex = Lexer::new(input);
     let mut parser = Parser::new(lex);
 
     let program = parser.parse_program().unwrap_or_else(|| panic!("parse_program() returned None"));
@@ -77,13 +129,17 @@ return 993322;";
         panic!("program.statements does not contain 3 statements. got={}", program.statements.len());
     }
 
-    for (_, statement) in program.statements.iter().enumerate() {
+    for (statement, value) in program.statements.iter().zip(expected) {
         match statement {
             ast::Statement::ReturnStatement(return_stmt) => {
                 if return_stmt.token_literal() != "return" {
                     eprintln!("return_stmt.token_literal not 'return', got {}", return_stmt.token_literal());
                     return;
                 }
+                match &return_stmt.return_value {
+                    Some(expression) if test_integer_literal(expression, value) => {}
+                    _ => panic!("return_stmt.return_value is not {}. got={:?}", value, return_stmt.return_value),
+                }
             }
             _ => {
                 eprintln!("statement not ast::Statement::ReturnStatement, got {:?}", statement);
@@ -93,11 +149,12 @@ return 993322;";
     }
 }
 
+#[allow(clippy::print_literal)]
 #[test]
 fn test_identifier_expression() {
     let input = "foobar;";
 
-    let lex = Lexer::new(input.to_string());
+    let lex = Lexer::new(input);
     let mut parser = Parser::new(lex);
     let program = parser.parse_program().unwrap_or_else(|| panic!("parse_program() returned None"));
     check_parser_errors(parser);
@@ -124,11 +181,12 @@ fn test_identifier_expression() {
     }
 }
 
+#[allow(clippy::print_literal)]
 #[test]
 fn test_integer_literal_expression() {
     let input = String::from("5");
 
-    let lex = Lexer::new(input);
+    let lex = Lexer::new(&input);
     let mut p = Parser::new(lex);
     let program = p.parse_program().unwrap_or_else(|| panic!("parse_program() returned None"));
     check_parser_errors(p);
@@ -155,4 +213,917 @@ fn test_integer_literal_expression() {
         _ => panic!("program.Statements.[0] is not ast::ExpressionStatement. got={:?}", program.statements[0]),
 
     }
-}
\ No newline at end of file
+}
+
+fn test_integer_literal(expression: &ast::Expression, value: i64) -> bool {
+    match expression {
+        ast::Expression::IntegerLiteral(int) => {
+            if int.value != value {
+                eprintln!("int.value not {}. got={}", value, int.value);
+                false
+            } else if int.token_literal() != value.to_string() {
+                eprintln!("int.token_literal not {}. got={}", value, int.token_literal());
+                false
+            } else {
+                true
+            }
+        }
+        _ => {
+            eprintln!("expression not ast::IntegerLiteral. got={:?}", expression);
+            false
+        }
+    }
+}
+
+fn parse_single_expression(input: &str) -> ast::Expression {
+    let lex = Lexer::new(input);
+    let mut parser = Parser::new(lex);
+    let program = parser.parse_program().unwrap_or_else(|| panic!("parse_program() returned None"));
+    check_parser_errors(parser);
+
+    if program.statements.len() != 1 {
+        panic!("program.statements does not contain 1 statement. got={}", program.statements.len());
+    }
+
+    match program.statements.into_iter().next().unwrap() {
+        ast::Statement::ExpressionStatement(ast::ExpressionStatement { expression: Some(expression), .. }) => expression,
+        statement => panic!("program.statements[0] is not ast::ExpressionStatement. got={:?}", statement),
+    }
+}
+
+#[test]
+fn test_parsing_prefix_expressions() {
+    let tests = [("!5;", "!", 5), ("-15;", "-", 15)];
+
+    for (input, operator, value) in tests {
+        match parse_single_expression(input) {
+            ast::Expression::PrefixExpression(prefix) => {
+                if prefix.operator != operator {
+                    panic!("prefix.operator is not '{}'. got={}", operator, prefix.operator);
+                }
+                if !test_integer_literal(&prefix.right, value) {
+                    panic!("prefix.right is not {}", value);
+                }
+            }
+            expression => panic!("expression is not ast::PrefixExpression. got={:?}", expression),
+        }
+    }
+}
+
+#[test]
+fn test_parsing_infix_expressions() {
+    let tests = [
+        ("5 + 5;", 5, "+", 5),
+        ("5 - 5;", 5, "-", 5),
+        ("5 * 5;", 5, "*", 5),
+        ("5 / 5;", 5, "/", 5),
+        ("5 > 5;", 5, ">", 5),
+        ("5 < 5;", 5, "<", 5),
+        ("5 == 5;", 5, "==", 5),
+        ("5 != 5;", 5, "!=", 5),
+    ];
+
+    for (input, left, operator, right) in tests {
+        match parse_single_expression(input) {
+            ast::Expression::InfixExpression(infix) => {
+                if !test_integer_literal(&infix.left, left) {
+                    panic!("infix.left is not {}", left);
+                }
+                if infix.operator != operator {
+                    panic!("infix.operator is not '{}'. got={}", operator, infix.operator);
+                }
+                if !test_integer_literal(&infix.right, right) {
+                    panic!("infix.right is not {}", right);
+                }
+            }
+            expression => panic!("expression is not ast::InfixExpression. got={:?}", expression),
+        }
+    }
+}
+
+#[test]
+fn test_infix_binding_power() {
+    // 1 + 2 * 3 must group as 1 + (2 * 3)
+    match parse_single_expression("1 + 2 * 3") {
+        ast::Expression::InfixExpression(sum) => {
+            if sum.operator != "+" || !test_integer_literal(&sum.left, 1) {
+                panic!("outer expression is not 1 + ... got={:?}", sum);
+            }
+            match *sum.right {
+                ast::Expression::InfixExpression(product) => {
+                    if product.operator != "*" || !test_integer_literal(&product.left, 2) || !test_integer_literal(&product.right, 3) {
+                        panic!("inner expression is not 2 * 3. got={:?}", product);
+                    }
+                }
+                right => panic!("sum.right is not ast::InfixExpression. got={:?}", right),
+            }
+        }
+        expression => panic!("expression is not ast::InfixExpression. got={:?}", expression),
+    }
+
+    // 1 - 2 - 3 must group as (1 - 2) - 3
+    match parse_single_expression("1 - 2 - 3") {
+        ast::Expression::InfixExpression(outer) => {
+            if !test_integer_literal(&outer.right, 3) {
+                panic!("outer.right is not 3. got={:?}", outer.right);
+            }
+            match *outer.left {
+                ast::Expression::InfixExpression(inner) => {
+                    if !test_integer_literal(&inner.left, 1) || !test_integer_literal(&inner.right, 2) {
+                        panic!("inner expression is not 1 - 2. got={:?}", inner);
+                    }
+                }
+                left => panic!("outer.left is not ast::InfixExpression. got={:?}", left),
+            }
+        }
+        expression => panic!("expression is not ast::InfixExpression. got={:?}", expression),
+    }
+
+    // -a * b must group as (-a) * b
+    match parse_single_expression("-5 * 10") {
+        ast::Expression::InfixExpression(product) => {
+            if !matches!(*product.left, ast::Expression::PrefixExpression(_)) {
+                panic!("product.left is not ast::PrefixExpression. got={:?}", product.left);
+            }
+        }
+        expression => panic!("expression is not ast::InfixExpression. got={:?}", expression),
+    }
+}
+
+#[test]
+fn test_node_spans() {
+    let input = "let x = 1;\nlet total = x +\n  20;";
+
+    let lex = Lexer::new(input);
+    let mut parser = Parser::new(lex);
+    let program = parser.parse_program().unwrap_or_else(|| panic!("parse_program() returned None"));
+    check_parser_errors(parser);
+
+    let span = program.statements[1].span();
+    if &input[span.start..span.end] != "let total = x +\n  20" {
+        panic!("statement span covers wrong text. got={:?}", &input[span.start..span.end]);
+    }
+    if (span.line, span.column) != (2, 1) {
+        panic!("statement span starts at wrong position. got={}", span);
+    }
+
+    match &program.statements[1] {
+        ast::Statement::LetStatement(ast::LetStatement { value: Some(ast::Expression::InfixExpression(infix)), .. }) => {
+            let right = infix.right.span();
+            if (right.line, right.column, right.start, right.end) != (3, 3, 29, 31) {
+                panic!("infix.right span wrong. got={:?}", right);
+            }
+        }
+        statement => panic!("program.statements[1] is not a let statement with an infix value. got={:?}", statement),
+    }
+}
+
+#[test]
+fn test_peek_error_reports_location() {
+    let lex = Lexer::new("let x = 1;\n  let 5 = 2;");
+    let mut parser = Parser::new(lex);
+    parser.parse_program();
+
+    let errors = parser.errors();
+    if errors.first().map(String::as_str) != Some("2:7: expected next token to be IDENT, got INT instead") {
+        panic!("wrong parser errors. got={:?}", errors);
+    }
+}
+
+#[test]
+fn test_error_recovery_reports_every_error() {
+    let input = "let x = );
+let = 5;
+let y = 10;
+x + ;
+return y
+let z = *3;
+z;";
+
+    let lex = Lexer::new(input);
+    let mut parser = Parser::new(lex);
+    let program = parser.parse_program().unwrap_or_else(|| panic!("parse_program() returned None"));
+
+    let expected = [
+        "1:9: no prefix parse function for RPAREN found",
+        "2:5: expected next token to be IDENT, got ASSIGN instead",
+        "4:5: no prefix parse function for SEMICOLON found",
+        "6:9: no prefix parse function for ASTERISK found",
+    ];
+    let errors = parser.errors();
+    if errors != expected {
+        panic!("wrong parser errors.\nexpected={:?}\ngot={:?}", expected, errors);
+    }
+
+    // let y, return y and z survive around the broken statements
+    if program.statements.len() != 3 {
+        panic!("program.statements does not contain 3 statements. got={:?}", program.statements);
+    }
+    if !test_let_statement(&program.statements[0], "y") {
+        panic!("program.statements[0] is not a let statement for y");
+    }
+}
+
+#[test]
+fn test_no_prefix_parse_fn_does_not_panic() {
+    for input in [";", ")", "}", "let x = 5; ) ;"] {
+        let lex = Lexer::new(input);
+        let mut parser = Parser::new(lex);
+        parser.parse_program();
+
+        if parser.errors().is_empty() {
+            panic!("{:?}: expected a parser error", input);
+        }
+    }
+}
+
+#[test]
+fn test_operator_precedence_parsing() {
+    let tests = [
+        ("-a * b", "((-a) * b)"),
+        ("!-a", "(!(-a))"),
+        ("a + b + c", "((a + b) + c)"),
+        ("a + b - c", "((a + b) - c)"),
+        ("a * b * c", "((a * b) * c)"),
+        ("a * b / c", "((a * b) / c)"),
+        ("a + b / c", "(a + (b / c))"),
+        ("a + b * c + d / e - f", "(((a + (b * c)) + (d / e)) - f)"),
+        ("3 + 4; -5 * 5", "(3 + 4)((-5) * 5)"),
+        ("5 > 4 == 3 < 4", "((5 > 4) == (3 < 4))"),
+        ("5 < 4 != 3 > 4", "((5 < 4) != (3 > 4))"),
+        ("3 + 4 * 5 == 3 * 1 + 4 * 5", "((3 + (4 * 5)) == ((3 * 1) + (4 * 5)))"),
+    ];
+
+    for (input, expected) in tests {
+        let lex = Lexer::new(input);
+        let mut parser = Parser::new(lex);
+        let program = parser.parse_program().unwrap_or_else(|| panic!("parse_program() returned None"));
+        check_parser_errors(parser);
+
+        let actual = program.to_string();
+        if actual != expected {
+            panic!("expected={:?}, got={:?}", expected, actual);
+        }
+    }
+}
+
+#[test]
+fn test_boolean_expression() {
+    let tests = [("true;", true), ("false;", false)];
+
+    for (input, value) in tests {
+        match parse_single_expression(input) {
+            ast::Expression::Boolean(boolean) => {
+                if boolean.value != value {
+                    panic!("boolean.value not {}. got={}", value, boolean.value);
+                }
+                if boolean.token_literal() != value.to_string() {
+                    panic!("boolean.token_literal not {}. got={}", value, boolean.token_literal());
+                }
+            }
+            expression => panic!("expression is not ast::Boolean. got={:?}", expression),
+        }
+    }
+}
+
+#[test]
+fn test_parsing_prefix_boolean_expressions() {
+    let tests = [("!true;", "!", true), ("!false;", "!", false), ("-true", "-", true)];
+
+    for (input, operator, value) in tests {
+        match parse_single_expression(input) {
+            ast::Expression::PrefixExpression(prefix) => {
+                if prefix.operator != operator {
+                    panic!("prefix.operator is not '{}'. got={}", operator, prefix.operator);
+                }
+                match *prefix.right {
+                    ast::Expression::Boolean(boolean) if boolean.value == value => {}
+                    right => panic!("prefix.right is not {}. got={:?}", value, right),
+                }
+            }
+            expression => panic!("expression is not ast::PrefixExpression. got={:?}", expression),
+        }
+    }
+}
+
+#[test]
+fn test_boolean_and_grouped_precedence_parsing() {
+    let tests = [
+        ("true", "true"),
+        ("false", "false"),
+        ("3 > 5 == false", "((3 > 5) == false)"),
+        ("3 < 5 == true", "((3 < 5) == true)"),
+        ("1 + (2 + 3) + 4", "((1 + (2 + 3)) + 4)"),
+        ("(5 + 5) * 2", "((5 + 5) * 2)"),
+        ("2 / (5 + 5)", "(2 / (5 + 5))"),
+        ("-(5 + 5)", "(-(5 + 5))"),
+        ("!(true == true)", "(!(true == true))"),
+        ("((a))", "a"),
+    ];
+
+    for (input, expected) in tests {
+        let lex = Lexer::new(input);
+        let mut parser = Parser::new(lex);
+        let program = parser.parse_program().unwrap_or_else(|| panic!("parse_program() returned None"));
+        check_parser_errors(parser);
+
+        let actual = program.to_string();
+        if actual != expected {
+            panic!("expected={:?}, got={:?}", expected, actual);
+        }
+    }
+}
+
+#[test]
+fn test_unclosed_group_reports_opening_paren() {
+    let lex = Lexer::new("(1 + 2;");
+    let mut parser = Parser::new(lex);
+    parser.parse_program();
+
+    let diagnostics = parser.diagnostics();
+    if diagnostics.len() != 1 {
+        panic!("expected 1 diagnostic. got={:?}", parser.errors());
+    }
+    if diagnostics[0].message != "expected next token to be RPAREN, got SEMICOLON instead" {
+        panic!("wrong message. got={}", diagnostics[0].message);
+    }
+    if !diagnostics[0].labels.iter().any(|label| label.message == "unclosed delimiter" && label.span.start == 0) {
+        panic!("missing unclosed delimiter label. got={:?}", diagnostics[0].labels);
+    }
+}
+
+fn test_identifier(expression: &ast::Expression, value: &str) -> bool {
+    match expression {
+        ast::Expression::Identifier(ident) if ident.value == value && ident.token_literal() == value => true,
+        _ => {
+            eprintln!("expression is not identifier {}. got={:?}", value, expression);
+            false
+        }
+    }
+}
+
+fn test_single_identifier_block(block: &ast::BlockStatement, value: &str) -> bool {
+    if block.statements.len() != 1 {
+        eprintln!("block does not contain 1 statement. got={}", block.statements.len());
+        return false;
+    }
+    match &block.statements[0] {
+        ast::Statement::ExpressionStatement(ast::ExpressionStatement { expression: Some(expression), .. }) => {
+            test_identifier(expression, value)
+        }
+        statement => {
+            eprintln!("block.statements[0] is not ast::ExpressionStatement. got={:?}", statement);
+            false
+        }
+    }
+}
+
+#[test]
+fn test_if_expression() {
+    match parse_single_expression("if (x < y) { x }") {
+        ast::Expression::IfExpression(if_expression) => {
+            if if_expression.condition.to_string() != "(x < y)" {
+                panic!("condition is not (x < y). got={}", if_expression.condition.to_string());
+            }
+            if !test_single_identifier_block(&if_expression.consequence, "x") {
+                panic!("consequence is not {{ x }}");
+            }
+            if let Some(alternative) = if_expression.alternative {
+                panic!("alternative was not None. got={:?}", alternative);
+            }
+        }
+        expression => panic!("expression is not ast::IfExpression. got={:?}", expression),
+    }
+}
+
+#[test]
+fn test_if_else_expression() {
+    match parse_single_expression("if (x < y) { x } else { y }") {
+        ast::Expression::IfExpression(if_expression) => {
+            if if_expression.condition.to_string() != "(x < y)" {
+                panic!("condition is not (x < y). got={}", if_expression.condition.to_string());
+            }
+            if !test_single_identifier_block(&if_expression.consequence, "x") {
+                panic!("consequence is not {{ x }}");
+            }
+            match &if_expression.alternative {
+                Some(alternative) if test_single_identifier_block(alternative, "y") => {}
+                alternative => panic!("alternative is not {{ y }}. got={:?}", alternative),
+            }
+            if if_expression.to_string() != "if (x < y) { x } else { y }" {
+                panic!("if_expression.to_string() wrong. got={}", if_expression.to_string());
+            }
+        }
+        expression => panic!("expression is not ast::IfExpression. got={:?}", expression),
+    }
+}
+
+#[test]
+fn test_if_expression_errors() {
+    let tests = [
+        ("if (x) { let = 1; y } z", vec!["1:14: expected next token to be IDENT, got ASSIGN instead"]),
+        ("if (x) { let y = }", vec!["1:18: no prefix parse function for RBRACE found"]),
+        ("if (x) { y", vec!["1:11: expected RBRACE, got EOF instead"]),
+        ("if x { y }", vec!["1:4: expected next token to be LPAREN, got IDENT instead"]),
+    ];
+
+    for (input, expected) in tests {
+        let lex = Lexer::new(input);
+        let mut parser = Parser::new(lex);
+        parser.parse_program();
+
+        if parser.errors() != expected {
+            panic!("{:?}: wrong parser errors.\nexpected={:?}\ngot={:?}", input, expected, parser.errors());
+        }
+    }
+}
+
+#[test]
+fn test_function_literal_parsing() {
+    match parse_single_expression("fn(x, y) { x + y; }") {
+        ast::Expression::FunctionLiteral(function) => {
+            if function.parameters.len() != 2 {
+                panic!("function literal parameters wrong. want 2, got={}", function.parameters.len());
+            }
+            if function.parameters[0].value != "x" || function.parameters[1].value != "y" {
+                panic!("function literal parameters are not x, y. got={:?}", function.parameters);
+            }
+            if function.body.statements.len() != 1 {
+                panic!("function.body.statements does not have 1 statement. got={}", function.body.statements.len());
+            }
+            if function.body.statements[0].to_string() != "(x + y)" {
+                panic!("function body is not (x + y). got={}", function.body.statements[0].to_string());
+            }
+        }
+        expression => panic!("expression is not ast::FunctionLiteral. got={:?}", expression),
+    }
+}
+
+#[test]
+fn test_function_parameter_parsing() {
+    let tests = [("fn() {};", vec![]), ("fn(x) {};", vec!["x"]), ("fn(x, y, z) {};", vec!["x", "y", "z"])];
+
+    for (input, expected) in tests {
+        match parse_single_expression(input) {
+            ast::Expression::FunctionLiteral(function) => {
+                let parameters: Vec<&str> = function.parameters.iter().map(|parameter| parameter.value.as_str()).collect();
+                if parameters != expected {
+                    panic!("{}: parameters wrong. want={:?}, got={:?}", input, expected, parameters);
+                }
+            }
+            expression => panic!("expression is not ast::FunctionLiteral. got={:?}", expression),
+        }
+    }
+}
+
+#[test]
+fn test_call_expression_parsing() {
+    match parse_single_expression("add(1, 2 * 3, 4 + 5);") {
+        ast::Expression::CallExpression(call) => {
+            if !test_identifier(&call.function, "add") {
+                panic!("call.function is not add");
+            }
+            let arguments: Vec<String> = call.arguments.iter().map(|argument| argument.to_string()).collect();
+            if arguments != ["1", "(2 * 3)", "(4 + 5)"] {
+                panic!("call.arguments wrong. got={:?}", arguments);
+            }
+        }
+        expression => panic!("expression is not ast::CallExpression. got={:?}", expression),
+    }
+}
+
+#[test]
+fn test_call_precedence_parsing() {
+    let tests = [
+        ("a + add(b * c) + d", "((a + add((b * c))) + d)"),
+        ("add(a, b, 1, 2 * 3, 4 + 5, add(6, 7 * 8))", "add(a, b, 1, (2 * 3), (4 + 5), add(6, (7 * 8)))"),
+        ("add(a + b + c * d / f + g)", "add((((a + b) + ((c * d) / f)) + g))"),
+        ("let add = fn(a, b) { a + b }; add(1, 2)", "let add = fn(a, b) { (a + b) };add(1, 2)"),
+        ("fn(x) { x }(5)", "fn(x) { x }(5)"),
+    ];
+
+    for (input, expected) in tests {
+        let lex = Lexer::new(input);
+        let mut parser = Parser::new(lex);
+        let program = parser.parse_program().unwrap_or_else(|| panic!("parse_program() returned None"));
+        check_parser_errors(parser);
+
+        let actual = program.to_string();
+        if actual != expected {
+            panic!("expected={:?}, got={:?}", expected, actual);
+        }
+    }
+}
+
+#[test]
+fn test_function_and_call_errors() {
+    let tests = [
+        ("fn(x, 1) { x }", vec!["1:7: expected next token to be IDENT, got INT instead"]),
+        ("fn(x y) { x }", vec!["1:6: expected next token to be RPAREN, got IDENT instead"]),
+        ("add(1, 2; 3", vec!["1:9: expected next token to be RPAREN, got SEMICOLON instead"]),
+    ];
+
+    for (input, expected) in tests {
+        let lex = Lexer::new(input);
+        let mut parser = Parser::new(lex);
+        parser.parse_program();
+
+        if parser.errors() != expected {
+            panic!("{:?}: wrong parser errors.\nexpected={:?}\ngot={:?}", input, expected, parser.errors());
+        }
+    }
+}
+
+#[test]
+fn test_radix_and_separated_integer_literals() {
+    let tests = [
+        ("0xFF", 255),
+        ("0Xff", 255),
+        ("0b1010", 10),
+        ("0o755", 493),
+        ("1_000_000", 1_000_000),
+        ("0xFFFF_FFFF", 0xFFFF_FFFF),
+        ("007", 7),
+        ("9223372036854775807", i64::MAX),
+        ("0x7FFF_FFFF_FFFF_FFFF", i64::MAX),
+    ];
+
+    for (input, value) in tests {
+        match parse_single_expression(input) {
+            ast::Expression::IntegerLiteral(int) => {
+                if int.value != value {
+                    panic!("int.value not {}. got={}", value, int.value);
+                }
+                if int.token_literal() != input {
+                    panic!("int.token_literal not {}. got={}", input, int.token_literal());
+                }
+            }
+            expression => panic!("exp not ast::IntegerLiteral. got={:?}", expression),
+        }
+    }
+}
+
+#[test]
+fn test_integer_literal_errors() {
+    let tests = [
+        ("0b102", "1:1: invalid digit '2' in binary literal 0b102"),
+        ("let mask = 0xFG;", "1:12: invalid digit 'G' in hexadecimal literal 0xFG"),
+        ("0o8", "1:1: invalid digit '8' in octal literal 0o8"),
+        ("0x", "1:1: missing digits in hexadecimal literal 0x"),
+        ("1__000", "1:1: misplaced `_` in integer literal 1__000"),
+        ("1_", "1:1: misplaced `_` in integer literal 1_"),
+        ("0x_FF", "1:1: misplaced `_` in integer literal 0x_FF"),
+        ("1_.5", "1:1: misplaced `_` in float literal 1_.5"),
+        ("1_e5", "1:1: misplaced `_` in float literal 1_e5"),
+        ("1.5_e3", "1:1: misplaced `_` in float literal 1.5_e3"),
+        ("2.5e1_", "1:1: misplaced `_` in float literal 2.5e1_"),
+        ("0xF_G", "1:1: invalid digit 'G' in hexadecimal literal 0xF_G"),
+        ("0b1_2", "1:1: invalid digit '2' in binary literal 0b1_2"),
+        ("let v = 1.2.3; v", "1:9: could not parse 1.2.3 as float"),
+        ("0x1.5", "1:1: invalid digit '.' in hexadecimal literal 0x1.5"),
+    ];
+
+    for (input, expected) in tests {
+        let lex = Lexer::new(input);
+        let mut parser = Parser::new(lex);
+        parser.parse_program();
+
+        if parser.errors() != [expected] {
+            panic!("{:?}: wrong parser errors.\nexpected={:?}\ngot={:?}", input, expected, parser.errors());
+        }
+    }
+}
+
+#[cfg(not(feature = "bigint"))]
+#[test]
+fn test_integer_literal_out_of_range() {
+    let tests = [
+        ("9223372036854775808", "1:1: integer literal 9223372036854775808 is too large for a 64-bit integer"),
+        ("0x1_0000_0000_0000_0000", "1:1: integer literal 0x1_0000_0000_0000_0000 is too large for a 64-bit integer"),
+    ];
+
+    for (input, expected) in tests {
+        let lex = Lexer::new(input);
+        let mut parser = Parser::new(lex);
+        parser.parse_program();
+
+        if parser.errors() != [expected] {
+            panic!("{:?}: wrong parser errors.\nexpected={:?}\ngot={:?}", input, expected, parser.errors());
+        }
+    }
+}
+
+#[cfg(feature = "bigint")]
+#[test]
+fn test_big_integer_literals() {
+    let tests = [
+        ("9223372036854775808", "9223372036854775808"),
+        ("0x1_0000_0000_0000_0000", "18446744073709551616"),
+        ("-99999999999999999999", "(-99999999999999999999)"),
+    ];
+
+    for (input, expected) in tests {
+        let lex = Lexer::new(input);
+        let mut parser = Parser::new(lex);
+        let program = parser.parse_program().unwrap_or_else(|| panic!("parse_program() returned None"));
+        check_parser_errors(parser);
+
+        if program.to_string() != expected {
+            panic!("expected={:?}, got={:?}", expected, program.to_string());
+        }
+    }
+
+    let lex = Lexer::new("0b1_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_2");
+    let mut parser = Parser::new(lex);
+    parser.parse_program();
+    if parser.errors().len() != 1 || !parser.errors()[0].contains("invalid digit '2'") {
+        panic!("malformed big literal not reported. got={:?}", parser.errors());
+    }
+}
+
+#[test]
+fn test_float_literal_expression() {
+    let tests = [
+        ("2.75", 2.75),
+        (".5", 0.5),
+        ("1e-9", 1e-9),
+        ("2.5E3", 2500.0),
+        ("1_000.000_5", 1000.0005),
+        ("1e1_0", 1e10),
+        ("6.0_2e-2_3", 6.02e-23),
+    ];
+
+    for (input, value) in tests {
+        match parse_single_expression(input) {
+            ast::Expression::FloatLiteral(float) => {
+                if float.value != value {
+                    panic!("float.value not {}. got={}", value, float.value);
+                }
+                if float.token_literal() != input {
+                    panic!("float.token_literal not {}. got={}", input, float.token_literal());
+                }
+            }
+            expression => panic!("exp not ast::FloatLiteral. got={:?}", expression),
+        }
+    }
+
+    let program = Parser::new(Lexer::new("-.5 * 2.0 + 1")).parse_program().unwrap();
+    if program.to_string() != "(((-.5) * 2.0) + 1)" {
+        panic!("wrong program string. got={:?}", program.to_string());
+    }
+}
+
+#[test]
+fn test_string_literal_expression() {
+    let tests = [
+        (r#""hello world";"#, "hello world"),
+        (r#""tab\tnew\nline""#, "tab\tnew\nline"),
+        (r#""quote \" and backslash \\""#, "quote \" and backslash \\"),
+        (r#""\u{48}\u{1F600}\u{e9}""#, "H\u{1F600}\u{e9}"),
+        (r#""""#, ""),
+    ];
+
+    for (input, expected) in tests {
+        match parse_single_expression(input) {
+            ast::Expression::StringLiteral(literal) => {
+                if literal.value != expected {
+                    panic!("literal.value not {:?}. got={:?}", expected, literal.value);
+                }
+                if literal.to_string() != input.trim_end_matches(';') {
+                    panic!("literal.to_string() does not keep the source spelling. got={}", literal.to_string());
+                }
+            }
+            expression => panic!("expression is not ast::StringLiteral. got={:?}", expression),
+        }
+    }
+}
+
+#[test]
+fn test_string_literal_errors() {
+    let tests = [
+        (r#"let s = "bad \q escape";"#, "1:14: invalid escape sequence \\q"),
+        (r#""\u{110000}""#, "1:2: invalid escape sequence \\u{110000}"),
+        (r#""\u{zz}""#, "1:2: invalid escape sequence \\u{"),
+        ("let s = \"never\nclosed;", "1:9: unterminated string literal"),
+    ];
+
+    for (input, expected) in tests {
+        let lex = Lexer::new(input);
+        let mut parser = Parser::new(lex);
+        parser.parse_program();
+
+        if parser.errors() != [expected] {
+            panic!("{:?}: wrong parser errors.\nexpected={:?}\ngot={:?}", input, expected, parser.errors());
+        }
+    }
+}
+
+#[test]
+fn test_nul_character_is_reported() {
+    let lex = Lexer::new("let a = 1; \0 let b = 2; b");
+    let mut parser = Parser::new(lex);
+    parser.parse_program();
+
+    let expected = ["1:12: illegal character \"\\0\""];
+    if parser.errors() != expected {
+        panic!("wrong parser errors.\nexpected={:?}\ngot={:?}", expected, parser.errors());
+    }
+}
+
+#[test]
+fn test_comment_errors() {
+    let tests = [
+        ("let x = 1;\n/* never closed", "2:1: unterminated block comment"),
+        ("let x = /* a /* b */ 1;", "1:9: unterminated block comment"),
+    ];
+
+    for (input, expected) in tests {
+        let lex = Lexer::new(input);
+        let mut parser = Parser::new(lex);
+        parser.parse_program();
+
+        if parser.errors() != [expected] {
+            panic!("{:?}: wrong parser errors.\nexpected={:?}\ngot={:?}", input, expected, parser.errors());
+        }
+    }
+}
+
+#[test]
+fn test_parsing_array_literals() {
+    match parse_single_expression("[1, 2 * 2, 3 + 3]") {
+        ast::Expression::ArrayLiteral(array) => {
+            let elements: Vec<String> = array.elements.iter().map(|element| element.to_string()).collect();
+            if elements != ["1", "(2 * 2)", "(3 + 3)"] {
+                panic!("array.elements wrong. got={:?}", elements);
+            }
+        }
+        expression => panic!("expression is not ast::ArrayLiteral. got={:?}", expression),
+    }
+
+    match parse_single_expression("[]") {
+        ast::Expression::ArrayLiteral(array) if array.elements.is_empty() => {}
+        expression => panic!("expression is not an empty ast::ArrayLiteral. got={:?}", expression),
+    }
+}
+
+#[test]
+fn test_parsing_index_expressions() {
+    match parse_single_expression("myArray[1 + 1]") {
+        ast::Expression::IndexExpression(index) => {
+            if !test_identifier(&index.left, "myArray") {
+                panic!("index.left is not myArray");
+            }
+            if index.index.to_string() != "(1 + 1)" {
+                panic!("index.index is not (1 + 1). got={}", index.index.to_string());
+            }
+        }
+        expression => panic!("expression is not ast::IndexExpression. got={:?}", expression),
+    }
+}
+
+#[test]
+fn test_index_precedence_parsing() {
+    let tests = [
+        ("a * [1, 2, 3, 4][b * c] * d", "((a * ([1, 2, 3, 4][(b * c)])) * d)"),
+        ("add(a * b[2], b[1], 2 * [1, 2][1])", "add((a * (b[2])), (b[1]), (2 * ([1, 2][1])))"),
+        ("getArray()[0]", "(getArray()[0])"),
+        ("-a[0]", "(-(a[0]))"),
+    ];
+
+    for (input, expected) in tests {
+        let lex = Lexer::new(input);
+        let mut parser = Parser::new(lex);
+        let program = parser.parse_program().unwrap_or_else(|| panic!("parse_program() returned None"));
+        check_parser_errors(parser);
+
+        let actual = program.to_string();
+        if actual != expected {
+            panic!("expected={:?}, got={:?}", expected, actual);
+        }
+    }
+}
+
+#[test]
+fn test_parsing_hash_literals() {
+    let tests = [
+        (r#"{"one": 1, "two": 2, "three": 3}"#, vec![(r#""one""#, "1"), (r#""two""#, "2"), (r#""three""#, "3")]),
+        (r#"{"one": 0 + 1, "two": 10 - 8}"#, vec![(r#""one""#, "(0 + 1)"), (r#""two""#, "(10 - 8)")]),
+        (r#"{1: true, true: "x", key: fn(a) { a }}"#, vec![("1", "true"), ("true", r#""x""#), ("key", "fn(a) { a }")]),
+        ("{}", vec![]),
+    ];
+
+    for (input, expected) in tests {
+        match parse_single_expression(input) {
+            ast::Expression::HashLiteral(hash) => {
+                let pairs: Vec<(String, String)> =
+                    hash.pairs.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect();
+                let expected: Vec<(String, String)> =
+                    expected.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect();
+                if pairs != expected {
+                    panic!("{}: hash.pairs wrong.\nexpected={:?}\ngot={:?}", input, expected, pairs);
+                }
+            }
+            expression => panic!("expression is not ast::HashLiteral. got={:?}", expression),
+        }
+    }
+}
+
+#[test]
+fn test_hash_literal_errors() {
+    let tests = [
+        (r#"{"a" 1}"#, vec!["1:6: expected next token to be COLON, got INT instead"]),
+        (r#"{"a": 1 "b": 2}"#, vec!["1:9: expected next token to be COMMA, got STRING instead"]),
+        (
+            r#"if (x) { {"a" 1}; let y = {1: {2 3}}; y } let z = 1;"#,
+            vec!["1:15: expected next token to be COLON, got INT instead", "1:34: expected next token to be COLON, got INT instead"],
+        ),
+    ];
+
+    for (input, expected) in tests {
+        let lex = Lexer::new(input);
+        let mut parser = Parser::new(lex);
+        parser.parse_program();
+
+        if parser.errors() != expected {
+            panic!("{:?}: wrong parser errors.\nexpected={:?}\ngot={:?}", input, expected, parser.errors());
+        }
+    }
+}
+
+#[test]
+fn test_trailing_commas_are_rejected() {
+    let tests = [
+        ("[1, 2,]", vec!["1:7: no prefix parse function for RBRACKET found"]),
+        ("f(1,)", vec!["1:5: no prefix parse function for RPAREN found"]),
+        ("fn(x,) { x }", vec!["1:6: expected next token to be IDENT, got RPAREN instead"]),
+        (r#"{"a": 1, }"#, vec!["1:10: no prefix parse function for RBRACE found"]),
+    ];
+
+    for (input, expected) in tests {
+        let lex = Lexer::new(input);
+        let mut parser = Parser::new(lex);
+        parser.parse_program();
+
+        if parser.errors() != expected {
+            panic!("{:?}: wrong parser errors.\nexpected={:?}\ngot={:?}", input, expected, parser.errors());
+        }
+    }
+}
+
+#[test]
+fn test_logical_and_comparison_precedence_parsing() {
+    let tests = [
+        ("a <= b", "(a <= b)"),
+        ("a + 1 >= b * 2", "((a + 1) >= (b * 2))"),
+        ("a < b == c >= d", "((a < b) == (c >= d))"),
+        ("a && b || c", "((a && b) || c)"),
+        ("a || b && c", "(a || (b && c))"),
+        ("a == b && c != d", "((a == b) && (c != d))"),
+        ("a || b || c", "((a || b) || c)"),
+        ("!a && b", "((!a) && b)"),
+    ];
+
+    for (input, expected) in tests {
+        let lex = Lexer::new(input);
+        let mut parser = Parser::new(lex);
+        let program = parser.parse_program().unwrap_or_else(|| panic!("parse_program() returned None"));
+        check_parser_errors(parser);
+
+        let actual = program.to_string();
+        if actual != expected {
+            panic!("expected={:?}, got={:?}", expected, actual);
+        }
+    }
+}
+
+#[test]
+fn test_arithmetic_and_bitwise_precedence_parsing() {
+    let tests = [
+        ("a % b * c", "((a % b) * c)"),
+        ("a + b % c", "(a + (b % c))"),
+        ("a ** b ** c", "(a ** (b ** c))"),
+        ("a * b ** c", "(a * (b ** c))"),
+        ("-a ** b", "(-(a ** b))"),
+        ("a ** -b", "(a ** (-b))"),
+        ("f(a) ** b[0]", "(f(a) ** (b[0]))"),
+        ("a | b ^ c & d", "(a | (b ^ (c & d)))"),
+        ("a & b == 0", "((a & b) == 0)"),
+        ("a << b + c", "(a << (b + c))"),
+        ("a >> 1 & 255", "((a >> 1) & 255)"),
+        ("a & b << c >> d", "(a & ((b << c) >> d))"),
+        ("~a & b", "((~a) & b)"),
+        ("a | b < c || d", "(((a | b) < c) || d)"),
+    ];
+
+    for (input, expected) in tests {
+        let lex = Lexer::new(input);
+        let mut parser = Parser::new(lex);
+        let program = parser.parse_program().unwrap_or_else(|| panic!("parse_program() returned None"));
+        check_parser_errors(parser);
+
+        let actual = program.to_string();
+        if actual != expected {
+            panic!("expected={:?}, got={:?}", expected, actual);
+        }
+    }
+}
@@ -1,26 +1,77 @@
-use crate::token;
-use crate::lexer;
-use std::{ io};
-use std::io::{BufRead};
+#[cfg(test)]
+mod mod_test;
+
+use std::cell::RefCell;
+use std::io;
+use std::rc::Rc;
 
+use crate::evaluator::{self, Environment, Object};
+use crate::lexer;
+use crate::parser;
+use crate::token::TokenType;
 
 const PROMPT: &str = ">> ";
+const CONTINUATION_PROMPT: &str = ".. ";
 
-pub fn start<R: io::BufRead, W: io::Write>(input: R, mut output: W) {
-    let scanner = io::BufReader::new(input);
-    for line in scanner.lines() {
-        write!(output, "{}", PROMPT).expect("Error writing to output");
-        let line = line.expect("Error reading line");
+pub fn start<R: io::BufRead, W: io::Write>(input: R, output: W) {
+    start_with_env(input, output, &Rc::new(RefCell::new(Environment::new())));
+}
 
-        let mut lexer = lexer::Lexer::new(line.to_string());
+/// Reads, evaluates and prints one input at a time until EOF. Bindings made
+/// by earlier inputs stay visible in `env`. Lines are joined while brackets
+/// are left open, so functions can span lines.
+pub fn start_with_env<R: io::BufRead, W: io::Write>(input: R, mut output: W, env: &Rc<RefCell<Environment>>) {
+    let mut lines = input.lines();
+    loop {
+        prompt(&mut output, PROMPT);
+        let mut source = match lines.next() {
+            Some(line) => line.expect("Error reading line"),
+            None => break,
+        };
+        while is_incomplete(&source) {
+            prompt(&mut output, CONTINUATION_PROMPT);
+            match lines.next() {
+                Some(line) => {
+                    source.push('\n');
+                    source.push_str(&line.expect("Error reading line"));
+                }
+                // Let the parser report whatever is left open.
+                None => break,
+            }
+        }
 
-        loop {
-            let tok = lexer.next_token();
-            if tok.token_type == token::TokenType::EOF {
-                break;
+        let mut parser = parser::Parser::new(lexer::Lexer::new(&source));
+        let program = parser.parse_program();
+        if !parser.diagnostics().is_empty() {
+            for diagnostic in parser.diagnostics() {
+                write!(output, "{}", diagnostic.render("<repl>", &source)).expect("Error writing to output");
             }
-            writeln!(output, "{:?}", tok).expect("Error writing to output");
+            continue;
+        }
+
+        match program.map(|program| evaluator::eval_program(&program, env)) {
+            Some(Object::Null) | None => {}
+            Some(result) => writeln!(output, "{}", result).expect("Error writing to output"),
         }
     }
+    writeln!(output).expect("Error writing to output");
+}
+
+fn prompt<W: io::Write>(output: &mut W, prompt: &str) {
+    write!(output, "{}", prompt).expect("Error writing to output");
+    output.flush().expect("Error writing to output");
 }
 
+/// Whether `source` ends inside an unclosed `(`, `[` or `{`. Extra closing
+/// delimiters, unterminated strings and comments are left to the parser.
+fn is_incomplete(source: &str) -> bool {
+    let mut depth = 0;
+    for token in lexer::Lexer::new(source) {
+        match token.token_type {
+            TokenType::LPAREN | TokenType::LBRACKET | TokenType::LBRACE => depth += 1,
+            TokenType::RPAREN | TokenType::RBRACKET | TokenType::RBRACE => depth -= 1,
+            _ => {}
+        }
+    }
+    depth > 0
+}
@@ -0,0 +1,66 @@
+use super::start;
+
+fn run(input: &str) -> String {
+    let mut output = Vec::new();
+    start(input.as_bytes(), &mut output);
+    String::from_utf8(output).expect("REPL output is not UTF-8")
+}
+
+#[test]
+fn test_evaluates_each_line() {
+    let output = run("1 + 2\nlet x = 5;\nx * 2\n");
+    let expected = ">> 3\n>> >> 10\n>> \n";
+    if output != expected {
+        panic!("wrong REPL output.\nexpected={:?}\ngot={:?}", expected, output);
+    }
+}
+
+#[test]
+fn test_environment_persists_across_inputs() {
+    let output = run("let add = fn(a, b) { a + b };\nlet three = add(1, 2);\nadd(three, 4)\n");
+    let expected = ">> >> >> 7\n>> \n";
+    if output != expected {
+        panic!("wrong REPL output.\nexpected={:?}\ngot={:?}", expected, output);
+    }
+}
+
+#[test]
+fn test_continuation_lines() {
+    let input = "let max = fn(a, b) {\n  if (a > b) {\n    a\n  } else { b }\n};\nmax(3,\n 9)\nlen([\"two\",\n\"lines\"])\n";
+    let output = run(input);
+    let expected = ">> .. .. .. .. >> .. 9\n>> .. 2\n>> \n";
+    if output != expected {
+        panic!("wrong REPL output.\nexpected={:?}\ngot={:?}", expected, output);
+    }
+}
+
+#[test]
+fn test_prints_diagnostics_and_runtime_errors() {
+    let output = run("let = 5;\n5 + true\n1\n");
+    let expected = ">> error[E0001]: expected next token to be IDENT, got ASSIGN instead\n --> <repl>:1:5\n";
+    if !output.starts_with(expected) {
+        panic!("diagnostic not rendered.\nexpected prefix={:?}\ngot={:?}", expected, output);
+    }
+    if !output.ends_with(">> ERROR: type mismatch: INTEGER + BOOLEAN\n>> 1\n>> \n") {
+        panic!("wrong REPL output after the diagnostic. got={:?}", output);
+    }
+}
+
+#[test]
+fn test_unclosed_input_at_eof_is_reported() {
+    let output = run("fn(x) {\n  x\n");
+    if !output.starts_with(">> .. .. error[E0001]: expected RBRACE, got EOF instead") {
+        panic!("unclosed input not reported. got={:?}", output);
+    }
+}
+
+#[test]
+fn test_unterminated_string_does_not_swallow_later_lines() {
+    let output = run("let s = \"abc\n1 + 2\n");
+    if !output.starts_with(">> error[E0005]: unterminated string literal") {
+        panic!("unterminated string not reported. got={:?}", output);
+    }
+    if !output.ends_with(">> 3\n>> \n") {
+        panic!("line after the unterminated string not evaluated. got={:?}", output);
+    }
+}
@@ -27,10 +27,13 @@ use std::fmt;
 pub enum TokenType {
     ILLEGAL,
     EOF,
+    COMMENT,
 
     // Identifiers + literals
     IDENT,
     INT,
+    FLOAT,
+    STRING,
 
     // Operators
     ASSIGN,
@@ -43,14 +46,29 @@ pub enum TokenType {
     GT,
     EQ,
     NOT_EQ,
+    LT_EQ,
+    GT_EQ,
+    AND,
+    OR,
+    PERCENT,
+    POWER,
+    BIT_AND,
+    BIT_OR,
+    BIT_XOR,
+    SHL,
+    SHR,
+    TILDE,
 
     // Delimiters
     COMMA,
     SEMICOLON,
+    COLON,
     LPAREN,
     RPAREN,
     LBRACE,
     RBRACE,
+    LBRACKET,
+    RBRACKET,
 
     // keywords
     FUNCTION,
@@ -67,8 +85,11 @@ impl fmt::Display for TokenType {
         match self {
             TokenType::ILLEGAL => write!(f, "ILLEGAL"),
             TokenType::EOF => write!(f, "EOF"),
+            TokenType::COMMENT => write!(f, "COMMENT"),
             TokenType::IDENT => write!(f, "IDENT"),
             TokenType::INT => write!(f, "INT"),
+            TokenType::FLOAT => write!(f, "FLOAT"),
+            TokenType::STRING => write!(f, "STRING"),
             TokenType::ASSIGN => write!(f, "ASSIGN"),
             TokenType::PLUS => write!(f, "PLUS"),
             TokenType::MINUS => write!(f, "MINUS"),
@@ -79,12 +100,27 @@ impl fmt::Display for TokenType {
             TokenType::LT => write!(f, "LT"),
             TokenType::EQ => write!(f, "EQ"),
             TokenType::NOT_EQ => write!(f, "NOT_EQ"),
+            TokenType::LT_EQ => write!(f, "LT_EQ"),
+            TokenType::GT_EQ => write!(f, "GT_EQ"),
+            TokenType::AND => write!(f, "AND"),
+            TokenType::OR => write!(f, "OR"),
+            TokenType::PERCENT => write!(f, "PERCENT"),
+            TokenType::POWER => write!(f, "POWER"),
+            TokenType::BIT_AND => write!(f, "BIT_AND"),
+            TokenType::BIT_OR => write!(f, "BIT_OR"),
+            TokenType::BIT_XOR => write!(f, "BIT_XOR"),
+            TokenType::SHL => write!(f, "SHL"),
+            TokenType::SHR => write!(f, "SHR"),
+            TokenType::TILDE => write!(f, "TILDE"),
             TokenType::COMMA => write!(f, "COMMA"),
             TokenType::SEMICOLON => write!(f, "SEMICOLON"),
+            TokenType::COLON => write!(f, "COLON"),
             TokenType::LPAREN => write!(f, "LPAREN"),
             TokenType::RPAREN => write!(f, "RPAREN"),
             TokenType::LBRACE => write!(f, "LBRACE"),
             TokenType::RBRACE => write!(f, "RBRACE"),
+            TokenType::LBRACKET => write!(f, "LBRACKET"),
+            TokenType::RBRACKET => write!(f, "RBRACKET"),
             TokenType::FUNCTION => write!(f, "FUNCTION"),
             TokenType::LET => write!(f, "LET"),
             TokenType::IF => write!(f, "IF"),
@@ -97,20 +133,69 @@ impl fmt::Display for TokenType {
     }
 }
 
+/// Location of a piece of source text. `start` and `end` are byte offsets
+/// (end exclusive), `line` and `column` are 1-based and point at `start`.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize, line: usize, column: usize) -> Span {
+        Span { start, end, line, column }
+    }
+
+    /// Smallest span covering both `self` and `other`.
+    pub fn to(self, other: Span) -> Span {
+        let (first, last) = if other.start < self.start { (other, self) } else { (self, other) };
+        Span { start: first.start, end: first.end.max(last.end), line: first.line, column: first.column }
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
 #[derive(Debug)]
 pub struct Token {
     pub token_type: TokenType,
     pub literal: String,
+    pub span: Span,
 }
 
 impl Token {
    pub fn new(token_type: TokenType, character: char) -> Token {
-        Token { token_type, literal: character.to_string() }
+        Token { token_type, literal: character.to_string(), span: Span::default() }
     }
 }
 
 impl Clone for Token {
     fn clone(&self) -> Self {
-        Token { token_type: self.token_type.clone(), literal: self.literal.clone() }
+        Token { token_type: self.token_type, literal: self.literal.clone(), span: self.span }
+    }
+}
+
+/// Token whose literal borrows from the lexed source instead of allocating.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BorrowedToken<'src> {
+    pub token_type: TokenType,
+    pub literal: &'src str,
+    pub span: Span,
+}
+
+impl BorrowedToken<'_> {
+    pub fn to_owned_token(&self) -> Token {
+        Token { token_type: self.token_type, literal: self.literal.to_string(), span: self.span }
+    }
+}
+
+impl From<BorrowedToken<'_>> for Token {
+    fn from(token: BorrowedToken<'_>) -> Token {
+        token.to_owned_token()
     }
 }
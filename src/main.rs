@@ -1,9 +1,74 @@
+use std::cell::RefCell;
 use std::env;
+use std::fs;
 use std::io::{self};
+use std::process;
+use std::rc::Rc;
+use std::thread;
 
+use monkey::evaluator::{self, Environment, Object, Overflow};
+use monkey::lexer::Lexer;
+use monkey::parser::Parser;
 use monkey::repl;
 
+/// Stack size of the interpreter thread. Evaluation recurses on the native
+/// stack, and the main thread's default is too small for the deepest
+/// nesting of calls the evaluator allows in an unoptimized build.
+const STACK_SIZE: usize = 256 * 1024 * 1024;
+
+fn run_file(path: &str, overflow: Overflow) {
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(err) => {
+            eprintln!("could not read {}: {}", path, err);
+            process::exit(1);
+        }
+    };
+
+    let mut parser = Parser::new(Lexer::new(&source));
+    let program = parser.parse_program();
+    if !parser.diagnostics().is_empty() {
+        for diagnostic in parser.diagnostics() {
+            eprint!("{}", diagnostic.render(path, &source));
+        }
+        process::exit(1);
+    }
+
+    let env = Rc::new(RefCell::new(Environment::with_overflow(overflow)));
+    match program.map(|program| evaluator::eval_program(&program, &env)) {
+        Some(Object::Error(message)) => {
+            eprintln!("ERROR: {}", message);
+            process::exit(1);
+        }
+        Some(Object::Null) | None => {}
+        Some(result) => println!("{}", result),
+    }
+}
+
 fn main() {
+    let interpreter = thread::Builder::new().stack_size(STACK_SIZE).spawn(run).expect("Error spawning interpreter thread");
+    if interpreter.join().is_err() {
+        process::exit(101);
+    }
+}
+
+fn run() {
+    // `--checked` makes integer overflow a runtime error instead of
+    // wrapping (or promoting, with the `bigint` feature).
+    let mut overflow = Overflow::default();
+    let mut path = None;
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--checked" => overflow = Overflow::Error,
+            _ => path = Some(arg),
+        }
+    }
+
+    if let Some(path) = path {
+        run_file(&path, overflow);
+        return;
+    }
+
     let user = match env::var("USER") {
         Ok(user) => user,
         Err(_) => String::from("User"),
@@ -15,5 +80,6 @@ fn main() {
     let stdin = io::stdin();
     let stdout = io::stdout();
 
-    repl::start(stdin.lock(), stdout.lock());
-}
\ No newline at end of file
+    let env = Rc::new(RefCell::new(Environment::with_overflow(overflow)));
+    repl::start_with_env(stdin.lock(), stdout.lock(), &env);
+}